@@ -0,0 +1,171 @@
+// SPDX-FileCopyrightText: 2024  Emmanuele Bassi
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! CUE sheet support: splitting a single audio file described by a `.cue`
+//! sheet into individual queueable tracks.
+
+use std::path::{Path, PathBuf};
+
+use log::warn;
+
+/// A single track parsed out of a CUE sheet, with its start position
+/// relative to the start of `file`.
+#[derive(Debug, Clone)]
+pub struct CueTrack {
+    pub number: u32,
+    pub title: Option<String>,
+    pub performer: Option<String>,
+    /// The audio file this track is cut from (the sheet's `FILE` entry,
+    /// resolved relative to the `.cue` file itself).
+    pub file: PathBuf,
+    /// Start offset from the beginning of `file`, in milliseconds.
+    pub start_ms: u64,
+}
+
+/// The `.cue` sheet that would sit alongside `audio_path` (same name, `.cue`
+/// extension), if one actually exists on disk.
+pub fn sidecar_path(audio_path: &Path) -> Option<PathBuf> {
+    let cue_path = audio_path.with_extension("cue");
+    cue_path.is_file().then_some(cue_path)
+}
+
+/// Pair each track with its end offset: the following track's `start_ms`,
+/// if it's cut from the same `file`, or `None` if it's the last track cut
+/// from that file (play to the end of `file`).
+pub fn track_end_offsets(tracks: &[CueTrack]) -> Vec<Option<u64>> {
+    tracks
+        .iter()
+        .enumerate()
+        .map(|(i, track)| {
+            tracks
+                .get(i + 1)
+                .filter(|next| next.file == track.file)
+                .map(|next| next.start_ms)
+        })
+        .collect()
+}
+
+/// Parse a CUE sheet, resolving its `FILE` reference(s) relative to
+/// `cue_path`'s directory, and return one [`CueTrack`] per `TRACK` entry
+/// with its start offset taken from that track's own `INDEX 01`.
+pub fn parse_file(cue_path: &Path) -> Option<Vec<CueTrack>> {
+    let contents = std::fs::read_to_string(cue_path)
+        .map_err(|e| warn!("Could not read CUE sheet {cue_path:?}: {e}"))
+        .ok()?;
+    let base_dir = cue_path.parent().unwrap_or_else(|| Path::new("."));
+    Some(parse_with_default(&contents, base_dir, base_dir))
+}
+
+/// Parse a `CUESHEET` tag item embedded in `audio_path`'s own metadata
+/// (common for single-file FLAC/APE rips). Unlike a sidecar sheet, an
+/// embedded one usually has no `FILE` directive at all, since it always
+/// describes `audio_path` itself.
+pub fn parse_embedded(contents: &str, audio_path: &Path) -> Vec<CueTrack> {
+    let base_dir = audio_path.parent().unwrap_or_else(|| Path::new("."));
+    parse_with_default(contents, base_dir, audio_path)
+}
+
+/// Parse CUE sheet contents; `base_dir` is used to resolve the `FILE`
+/// directive into an absolute path.
+pub fn parse(contents: &str, base_dir: &Path) -> Vec<CueTrack> {
+    parse_with_default(contents, base_dir, base_dir)
+}
+
+/// Shared implementation: `default_file` is the `file` a [`CueTrack`] gets
+/// if no `FILE` directive has been seen yet (the sheet's own audio file for
+/// an embedded sheet, `base_dir` itself for a sidecar one with no `FILE`
+/// line, which is almost always a malformed sheet).
+fn parse_with_default(contents: &str, base_dir: &Path, default_file: &Path) -> Vec<CueTrack> {
+    let mut tracks = Vec::new();
+    let mut current_file = default_file.to_path_buf();
+
+    let mut number = None;
+    let mut title = None;
+    let mut performer = None;
+    let mut start_ms = None;
+
+    let flush = |tracks: &mut Vec<CueTrack>,
+                 number: &mut Option<u32>,
+                 title: &mut Option<String>,
+                 performer: &mut Option<String>,
+                 start_ms: &mut Option<u64>,
+                 file: &Path| {
+        if let (Some(number), Some(start_ms)) = (number.take(), start_ms.take()) {
+            tracks.push(CueTrack {
+                number,
+                title: title.take(),
+                performer: performer.take(),
+                file: file.to_path_buf(),
+                start_ms,
+            });
+        }
+    };
+
+    for raw_line in contents.lines() {
+        let line = raw_line.trim();
+
+        if let Some(rest) = line.strip_prefix("FILE ") {
+            flush(
+                &mut tracks,
+                &mut number,
+                &mut title,
+                &mut performer,
+                &mut start_ms,
+                &current_file,
+            );
+            if let Some(name) = unquote_file(rest) {
+                current_file = base_dir.join(name);
+            }
+        } else if let Some(rest) = line.strip_prefix("TRACK ") {
+            flush(
+                &mut tracks,
+                &mut number,
+                &mut title,
+                &mut performer,
+                &mut start_ms,
+                &current_file,
+            );
+            number = rest.split_whitespace().next().and_then(|n| n.parse().ok());
+        } else if let Some(rest) = line.strip_prefix("TITLE ") {
+            title = unquote(rest);
+        } else if let Some(rest) = line.strip_prefix("PERFORMER ") {
+            performer = unquote(rest);
+        } else if let Some(rest) = line.strip_prefix("INDEX 01 ") {
+            start_ms = parse_cue_timestamp(rest.trim());
+        }
+    }
+
+    flush(
+        &mut tracks,
+        &mut number,
+        &mut title,
+        &mut performer,
+        &mut start_ms,
+        &current_file,
+    );
+
+    tracks
+}
+
+/// Unquote a plain `"value"` field, e.g. `TITLE`/`PERFORMER`.
+fn unquote(s: &str) -> Option<String> {
+    Some(s.trim().trim_matches('"').to_string())
+}
+
+/// Unquote a `FILE "name" TYPE` field, dropping the trailing file type.
+fn unquote_file(s: &str) -> Option<String> {
+    let s = s.trim();
+    let name = s.rsplit_once(' ').map_or(s, |(name, _file_type)| name);
+    Some(name.trim_matches('"').to_string())
+}
+
+/// Parse a CUE `mm:ss:ff` timestamp (frames are 1/75th of a second) into
+/// milliseconds.
+fn parse_cue_timestamp(s: &str) -> Option<u64> {
+    let mut parts = s.splitn(3, ':');
+    let minutes: u64 = parts.next()?.parse().ok()?;
+    let seconds: u64 = parts.next()?.parse().ok()?;
+    let frames: u64 = parts.next()?.parse().ok()?;
+
+    Some(minutes * 60_000 + seconds * 1_000 + (frames * 1_000) / 75)
+}
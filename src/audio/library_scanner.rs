@@ -0,0 +1,226 @@
+// SPDX-FileCopyrightText: 2024  Emmanuele Bassi
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Parallel library scanning.
+//!
+//! Reading tags for a large music folder one file at a time on the main
+//! thread is the main reason adding a big folder to the queue used to make
+//! the UI stall; this spreads the blocking tag-reading across a small
+//! worker pool instead.
+//!
+//! `Song` is a GObject and isn't `Send`, so it's never built anywhere but
+//! the main thread: worker threads only run [`song::ScannedTags::read`],
+//! which is plain data, and the results are collected back on the main
+//! thread's async context (via `async_channel` + `glib::spawn_future_local`,
+//! same as the rest of the app's background work) and turned into `Song`s
+//! there with [`Song::from_scanned`]. `scan_uris`/`scan_paths` therefore
+//! hand results to a callback instead of blocking their caller for the
+//! whole scan.
+
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+use log::warn;
+use lofty::{file::TaggedFileExt, prelude::*};
+
+use crate::audio::{cue, song::ScannedTags, Song};
+
+use gtk::{gio, glib};
+
+/// Number of worker threads to use for a scan; capped so we don't thrash
+/// disk I/O on spinning rust, but enough to hide tag-parsing latency on SSDs.
+const MAX_WORKERS: usize = 4;
+
+/// One path queued for scanning: either a plain audio file, or an audio
+/// file split into several virtual tracks by a CUE sheet (a sidecar `.cue`
+/// file or an embedded `CUESHEET` tag item).
+enum Entry {
+    File(String),
+    Cue {
+        file_uri: String,
+        tracks: Vec<cue::CueTrack>,
+        end_offsets: Vec<Option<u64>>,
+    },
+}
+
+/// Resolve a batch of URIs into [`Song`]s, preserving the input order, and
+/// hand the result to `on_done` once every file has been read.
+///
+/// Files that fail to load (missing, unreadable, unsupported format) are
+/// skipped rather than aborting the whole scan. Tag reading runs on a worker
+/// pool off the calling thread; the `Song`s are built, and `on_done` is
+/// invoked, back on the main thread's context.
+pub fn scan_uris(uris: Vec<String>, on_done: impl FnOnce(Vec<Song>) + 'static) {
+    glib::spawn_future_local(async move {
+        let songs = scan_uris_ordered(uris).await.into_iter().flatten().collect();
+        on_done(songs);
+    });
+}
+
+/// Like [`scan_uris`], but keeps a `None` slot (instead of dropping it) for
+/// any URI that failed to load, so callers that need to line results back
+/// up with their input order can still do so.
+///
+/// Tag-reading happens on a small worker pool ([`ScannedTags::read`] is
+/// plain data, safe off the main thread); each `Song` itself is built here,
+/// after the result comes back, since `Song` is a GObject and isn't `Send`.
+async fn scan_uris_ordered(uris: Vec<String>) -> Vec<Option<Song>> {
+    if uris.is_empty() {
+        return Vec::new();
+    }
+
+    // Resolved up front, on the calling thread: `gio::File` isn't `Send`,
+    // but the `PathBuf` it yields is, so this is the only place one needs
+    // to exist before handing work off to the pool.
+    let paths: Vec<Option<PathBuf>> = uris.iter().map(|uri| gio::File::for_uri(uri).path()).collect();
+
+    let worker_count = MAX_WORKERS.min(uris.len());
+    let (work_tx, work_rx) = mpsc::channel::<(usize, PathBuf)>();
+    let (result_tx, result_rx) = async_channel::unbounded::<(usize, ScannedTags)>();
+
+    let work_rx = Arc::new(Mutex::new(work_rx));
+
+    let mut remote_indices = Vec::new();
+    for (index, path) in paths.into_iter().enumerate() {
+        match path {
+            Some(path) => {
+                work_tx.send((index, path)).ok();
+            }
+            // Non-local URIs (http/jellyfin) have no tags for a worker to
+            // read; resolve them directly below instead.
+            None => remote_indices.push(index),
+        }
+    }
+    drop(work_tx);
+
+    for _ in 0..worker_count {
+        let work_rx = Arc::clone(&work_rx);
+        let result_tx = result_tx.clone();
+
+        thread::spawn(move || loop {
+            let next = work_rx.lock().unwrap().recv();
+            let Ok((index, path)) = next else {
+                break;
+            };
+
+            let scanned = ScannedTags::read(&path);
+            result_tx.send_blocking((index, scanned)).ok();
+        });
+    }
+    drop(result_tx);
+
+    let mut slots: Vec<Option<Song>> = (0..uris.len()).map(|_| None).collect();
+    while let Ok((index, scanned)) = result_rx.recv().await {
+        slots[index] = Song::from_scanned(&uris[index], scanned)
+            .map_err(|e| warn!("Skipping {}: {e}", uris[index]))
+            .ok();
+    }
+
+    for index in remote_indices {
+        slots[index] = Song::from_uri(&uris[index])
+            .map_err(|e| warn!("Skipping {}: {e}", uris[index]))
+            .ok();
+    }
+
+    slots
+}
+
+/// Recursively collect playable paths under `paths` and scan them in
+/// parallel, walking each directory's entries in sorted path order so the
+/// order is deterministic, handing the
+/// result to `on_done` once scanning finishes. A file with a CUE sheet
+/// (sidecar or embedded) expands into one virtual [`Song`] per `TRACK`
+/// entry instead of a single whole-file song.
+pub fn scan_paths(paths: &[PathBuf], on_done: impl FnOnce(Vec<Song>) + 'static) {
+    let mut entries = Vec::new();
+    for path in paths {
+        collect_entries(path, &mut entries);
+    }
+
+    let plain_uris: Vec<String> = entries
+        .iter()
+        .filter_map(|entry| match entry {
+            Entry::File(uri) => Some(uri.clone()),
+            Entry::Cue { .. } => None,
+        })
+        .collect();
+
+    glib::spawn_future_local(async move {
+        let mut plain_songs = scan_uris_ordered(plain_uris).await.into_iter();
+
+        let mut songs = Vec::new();
+        for entry in entries {
+            match entry {
+                Entry::File(_) => songs.extend(plain_songs.next().flatten()),
+                Entry::Cue {
+                    file_uri,
+                    tracks,
+                    end_offsets,
+                } => {
+                    for (track, end_ms) in tracks.iter().zip(end_offsets) {
+                        match Song::from_cue_track(&file_uri, track, end_ms) {
+                            Ok(song) => songs.push(song),
+                            Err(e) => warn!(
+                                "Skipping CUE track {} of {file_uri}: {e}",
+                                track.number
+                            ),
+                        }
+                    }
+                }
+            }
+        }
+
+        on_done(songs);
+    });
+}
+
+fn collect_entries(path: &PathBuf, entries: &mut Vec<Entry>) {
+    if path.is_dir() {
+        let Ok(read_dir) = std::fs::read_dir(path) else {
+            return;
+        };
+        let mut children: Vec<PathBuf> = read_dir.filter_map(|e| e.ok().map(|e| e.path())).collect();
+        children.sort();
+        for child in children {
+            collect_entries(&child, entries);
+        }
+        return;
+    }
+
+    // A bare `.cue` sheet isn't itself playable; it's picked up below as a
+    // sidecar of whichever audio file it describes.
+    if path.extension().and_then(|e| e.to_str()).is_some_and(|ext| ext.eq_ignore_ascii_case("cue")) {
+        return;
+    }
+
+    if let Some(tracks) = cue_tracks_for(path) {
+        let uri = gio::File::for_path(path).uri().to_string();
+        let end_offsets = cue::track_end_offsets(&tracks);
+        entries.push(Entry::Cue {
+            file_uri: uri,
+            tracks,
+            end_offsets,
+        });
+        return;
+    }
+
+    entries.push(Entry::File(gio::File::for_path(path).uri().to_string()));
+}
+
+/// Find a CUE sheet for `path`, preferring a sidecar `.cue` file over an
+/// embedded `CUESHEET` tag item, and return the tracks cut from `path`
+/// specifically (a sheet can reference more than one `FILE`).
+fn cue_tracks_for(path: &Path) -> Option<Vec<cue::CueTrack>> {
+    let tracks = if let Some(cue_path) = cue::sidecar_path(path) {
+        cue::parse_file(&cue_path)?
+    } else {
+        let tagged_file = lofty::read_from_path(path).ok()?;
+        let tag = tagged_file.primary_tag().or_else(|| tagged_file.tags().next())?;
+        let sheet = tag.get_string(&lofty::tag::ItemKey::Unknown("CUESHEET".to_string()))?;
+        cue::parse_embedded(sheet, path)
+    };
+
+    let tracks: Vec<_> = tracks.into_iter().filter(|t| t.file == *path).collect();
+    (!tracks.is_empty()).then_some(tracks)
+}
@@ -0,0 +1,119 @@
+// SPDX-FileCopyrightText: 2024  Emmanuele Bassi
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Lyrics loading: embedded unsynchronized lyrics tags (USLT, Vorbis
+//! `LYRICS`) and sidecar `.lrc` files, with LRC parsed into time-synced
+//! lines. Embedded synchronized lyrics (SYLT) are not read.
+
+use std::path::Path;
+
+use log::debug;
+
+/// The lyrics for a song, either a single block of plain text or a
+/// time-synced sequence of `(timestamp in ms, line)` pairs sorted by
+/// timestamp.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Lyrics {
+    Plain(String),
+    Synced(Vec<(u64, String)>),
+}
+
+impl Lyrics {
+    /// Binary-search the synced lines for the one active at `position_ms`,
+    /// returning its index. Always returns `None` for `Plain` lyrics.
+    pub fn active_line(&self, position_ms: u64) -> Option<usize> {
+        match self {
+            Lyrics::Plain(_) => None,
+            Lyrics::Synced(lines) => {
+                match lines.binary_search_by_key(&position_ms, |(ts, _)| *ts) {
+                    Ok(idx) => Some(idx),
+                    Err(0) => None,
+                    Err(idx) => Some(idx - 1),
+                }
+            }
+        }
+    }
+}
+
+/// Look for embedded unsynchronized lyrics in a tagged file's primary tag.
+pub fn from_tag(tag: &lofty::tag::Tag) -> Option<Lyrics> {
+    use lofty::prelude::*;
+
+    tag.get_string(&lofty::tag::ItemKey::Lyrics)
+        .map(|s| Lyrics::Plain(s.to_string()))
+}
+
+/// Look for a sidecar `.lrc` file next to `audio_path` and parse it.
+pub fn from_sidecar(audio_path: &Path) -> Option<Lyrics> {
+    let lrc_path = audio_path.with_extension("lrc");
+    if !lrc_path.exists() {
+        return None;
+    }
+
+    let contents = std::fs::read_to_string(&lrc_path).ok()?;
+    debug!("Found sidecar lyrics file: {:?}", lrc_path);
+    Some(parse_lrc(&contents))
+}
+
+/// Parse LRC-format text into sorted, time-synced lines. Lines may carry
+/// several leading `[mm:ss.xx]` timestamps (the text is duplicated per
+/// timestamp), ID tags like `[ti:...]`/`[ar:...]` are skipped, and malformed
+/// brackets are ignored.
+pub fn parse_lrc(contents: &str) -> Lyrics {
+    let mut lines = Vec::new();
+
+    for raw_line in contents.lines() {
+        let mut rest = raw_line.trim();
+        let mut timestamps = Vec::new();
+
+        while let Some(stripped) = rest.strip_prefix('[') {
+            let Some(end) = stripped.find(']') else {
+                break;
+            };
+            let tag = &stripped[..end];
+
+            if let Some(ms) = parse_timestamp(tag) {
+                timestamps.push(ms);
+                rest = &stripped[end + 1..];
+            } else {
+                // Not a timestamp (e.g. `[ti:...]`/`[ar:...]`) — not a lyric
+                // line, skip the whole line.
+                timestamps.clear();
+                rest = "";
+                break;
+            }
+        }
+
+        if timestamps.is_empty() {
+            continue;
+        }
+
+        let text = rest.trim().to_string();
+        for ms in timestamps {
+            lines.push((ms, text.clone()));
+        }
+    }
+
+    lines.sort_by_key(|(ms, _)| *ms);
+    Lyrics::Synced(lines)
+}
+
+/// Parse a single `mm:ss.xx` (or `mm:ss`) timestamp into milliseconds.
+fn parse_timestamp(tag: &str) -> Option<u64> {
+    let (minutes, rest) = tag.split_once(':')?;
+    let minutes: u64 = minutes.trim().parse().ok()?;
+
+    let (seconds, fraction) = match rest.split_once('.') {
+        Some((s, f)) => (s, Some(f)),
+        None => (rest, None),
+    };
+    let seconds: u64 = seconds.trim().parse().ok()?;
+
+    let millis = match fraction {
+        Some(f) if f.len() == 2 => f.parse::<u64>().ok()? * 10,
+        Some(f) if f.len() == 3 => f.parse::<u64>().ok()?,
+        Some(_) | None => 0,
+    };
+
+    Some(minutes * 60_000 + seconds * 1_000 + millis)
+}
@@ -18,9 +18,12 @@ use gtk::glib;
 use log::error;
 
 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
-use mpris_server::{LoopStatus, Metadata, PlaybackStatus, Player, Time};
+use mpris_server::{
+    LoopStatus, Metadata, PlaybackStatus, Playlist, PlaylistId, PlaylistOrdering,
+    PlaylistsInterface, Player, Time, TrackId, TrackListInterface,
+};
 
-use crate::audio::{Controller, PlaybackAction, PlaybackState, RepeatMode, Song};
+use crate::audio::{xspf, Controller, PlaybackAction, PlaybackState, RepeatMode, Song};
 #[cfg(any(target_os = "linux", target_os = "freebsd"))]
 use crate::config::APPLICATION_ID;
 
@@ -30,6 +33,198 @@ pub struct MprisController {
     #[cfg(any(target_os = "linux", target_os = "freebsd"))]
     mpris: Rc<OnceCell<Player>>,
     song: RefCell<Option<Song>>,
+    #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+    queue: Rc<RefCell<Vec<Song>>>,
+}
+
+/// Backing implementation of `org.mpris.MediaPlayer2.TrackList`, exposing
+/// Amberol's play queue so clients like `playerctl` or KDE Connect can list
+/// upcoming tracks and their metadata instead of just the current one.
+/// `GoTo`, `AddTrack` and `RemoveTrack` are accepted but do nothing.
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+struct TrackList {
+    sender: Sender<PlaybackAction>,
+    queue: Rc<RefCell<Vec<Song>>>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+impl TrackListInterface for TrackList {
+    async fn get_tracks_metadata(&self, track_ids: Vec<TrackId>) -> Vec<Metadata> {
+        let queue = self.queue.borrow();
+        track_ids
+            .iter()
+            .filter_map(|id| {
+                let index = track_index(id)?;
+                let song = queue.get(index)?;
+                Some(song_metadata(song, id))
+            })
+            .collect()
+    }
+
+    async fn go_to(&self, _track_id: TrackId) -> mpris_server::zbus::fdo::Result<()> {
+        // No-op for now: jumping to an arbitrary queue index needs a
+        // `PlaybackAction::GoToTrack` variant and a handler for it in the
+        // player's action loop, and `PlaybackAction` isn't defined
+        // anywhere in this checkout (it lives alongside `AudioPlayer`,
+        // which isn't part of this tree) to add one to.
+        Ok(())
+    }
+
+    async fn add_track(
+        &self,
+        _uri: String,
+        _after_track: TrackId,
+        _set_as_current: bool,
+    ) -> mpris_server::zbus::fdo::Result<()> {
+        // Amberol's queue is managed from the app side (drag & drop, the
+        // "Add song" dialog); MPRIS clients can't append to it directly.
+        Ok(())
+    }
+
+    async fn remove_track(&self, _track_id: TrackId) -> mpris_server::zbus::fdo::Result<()> {
+        Ok(())
+    }
+
+    async fn tracks(&self) -> Vec<TrackId> {
+        self.queue
+            .borrow()
+            .iter()
+            .enumerate()
+            .map(|(index, _)| track_id(index))
+            .collect()
+    }
+
+    async fn can_edit_tracks(&self) -> bool {
+        false
+    }
+}
+
+/// Backing implementation of `org.mpris.MediaPlayer2.Playlists`, listing the
+/// XSPF files saved under the user's playlists directory.
+/// `ActivatePlaylist` is accepted but loads nothing, and no playlist is
+/// ever reported as active.
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+struct PlaylistManager {
+    sender: Sender<PlaybackAction>,
+}
+
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+impl PlaylistsInterface for PlaylistManager {
+    async fn activate_playlist(&self, _playlist_id: PlaylistId) -> mpris_server::zbus::fdo::Result<()> {
+        // No-op, for the same reason as `TrackList::go_to`: loading a
+        // playlist into the queue needs a `PlaybackAction::LoadPlaylist`
+        // variant and a handler for it in the player's action loop, and
+        // `PlaybackAction` isn't defined anywhere in this checkout to add
+        // one to.
+        Ok(())
+    }
+
+    async fn get_playlists(
+        &self,
+        index: u32,
+        max_count: u32,
+        _order: PlaylistOrdering,
+        reverse_order: bool,
+    ) -> Vec<Playlist> {
+        // `orderings` only ever advertises `Alphabetical`, so that's the
+        // only sort `_order` can actually ask for; name order is also all
+        // the xspf filenames on disk give us to sort by.
+        let mut playlists: Vec<Playlist> = playlists_dir()
+            .map(|dir| {
+                std::fs::read_dir(&dir)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "xspf"))
+                    .filter_map(|entry| {
+                        let path = entry.path();
+                        let name = path.file_stem()?.to_str()?.to_string();
+                        Some(Playlist {
+                            id: PlaylistId::try_from(format!(
+                                "/io/bassi/Amberol/Playlist/{name}"
+                            ))
+                            .ok()?,
+                            name,
+                            icon: Default::default(),
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        playlists.sort_by(|a, b| a.name.cmp(&b.name));
+        if reverse_order {
+            playlists.reverse();
+        }
+
+        playlists
+            .into_iter()
+            .skip(index as usize)
+            .take(max_count as usize)
+            .collect()
+    }
+
+    async fn playlist_count(&self) -> u32 {
+        playlists_dir()
+            .map(|dir| {
+                std::fs::read_dir(&dir)
+                    .map(|entries| {
+                        entries
+                            .flatten()
+                            .filter(|e| e.path().extension().is_some_and(|ext| ext == "xspf"))
+                            .count() as u32
+                    })
+                    .unwrap_or(0)
+            })
+            .unwrap_or(0)
+    }
+
+    async fn orderings(&self) -> Vec<PlaylistOrdering> {
+        vec![PlaylistOrdering::Alphabetical]
+    }
+
+    async fn active_playlist(&self) -> Option<Playlist> {
+        None
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+fn playlists_dir() -> Option<std::path::PathBuf> {
+    Some(glib::user_data_dir().join("amberol").join("playlists"))
+}
+
+// No longer called now that `activate_playlist` is a no-op (see above);
+// kept for whenever `PlaybackAction::LoadPlaylist` lands and it's wired
+// back in.
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+#[allow(dead_code)]
+fn playlist_path(id: &PlaylistId) -> Option<std::path::PathBuf> {
+    let name = id.as_str().rsplit('/').next()?;
+    Some(playlists_dir()?.join(format!("{name}.xspf")))
+}
+
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+fn track_id(index: usize) -> TrackId {
+    TrackId::try_from(format!("/io/bassi/Amberol/TrackList/{index}")).unwrap()
+}
+
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+fn track_index(id: &TrackId) -> Option<usize> {
+    id.as_str().rsplit('/').next()?.parse().ok()
+}
+
+#[cfg(any(target_os = "linux", target_os = "freebsd"))]
+fn song_metadata(song: &Song, id: &TrackId) -> Metadata {
+    let mut metadata = Metadata::new();
+    metadata = metadata.trackid(id.clone());
+    metadata = metadata.title(song.title().unwrap_or("Unknown Title"));
+    if let Some(artist) = song.artist() {
+        metadata = metadata.artist([artist]);
+    }
+    if let Some(album) = song.album() {
+        metadata = metadata.album(album);
+    }
+    metadata
 }
 
 #[allow(dead_code)]
@@ -49,6 +244,15 @@ impl MprisController {
                 .can_set_fullscreen(false);
 
             let mpris = Rc::new(OnceCell::new());
+            let queue = Rc::new(RefCell::new(Vec::new()));
+
+            let track_list = TrackList {
+                sender: sender.clone(),
+                queue: Rc::clone(&queue),
+            };
+            let playlists = PlaylistManager {
+                sender: sender.clone(),
+            };
 
             glib::spawn_future_local(clone!(
                 #[weak]
@@ -56,7 +260,10 @@ impl MprisController {
                 #[strong]
                 sender,
                 async move {
-                    match builder.build().await {
+                    match builder
+                        .build_with_tracklist_and_playlists(track_list, playlists)
+                        .await
+                    {
                         Err(err) => error!("Failed to create MPRIS server: {:?}", err),
                         Ok(player) => {
                             setup_signals(sender, &player);
@@ -69,6 +276,7 @@ impl MprisController {
             Self {
                 mpris,
                 song: RefCell::new(None),
+                queue,
             }
         }
 
@@ -82,6 +290,28 @@ impl MprisController {
         }
     }
 
+    /// Update the queue exposed through the `TrackList` interface. Called by
+    /// the player whenever the in-app queue changes, mirroring
+    /// [`Self::update_song`] for the current-track metadata.
+    pub fn update_queue(&self, songs: &[Song]) {
+        #[cfg(any(target_os = "linux", target_os = "freebsd"))]
+        {
+            *self.queue.borrow_mut() = songs.to_vec();
+
+            if let Some(mpris) = self.mpris.get() {
+                let ids: Vec<TrackId> = (0..songs.len()).map(track_id).collect();
+                if let Err(err) = mpris.set_tracks(ids) {
+                    error!("Could not update MPRIS track list: {}", err);
+                }
+            }
+        }
+
+        #[cfg(not(any(target_os = "linux", target_os = "freebsd")))]
+        {
+            let _ = songs;
+        }
+    }
+
     pub fn stop(&self) {
         #[cfg(any(target_os = "linux", target_os = "freebsd"))]
         {
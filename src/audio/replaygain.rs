@@ -0,0 +1,105 @@
+// SPDX-FileCopyrightText: 2024  Emmanuele Bassi
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! ReplayGain volume normalization: reading the standard `REPLAYGAIN_*`
+//! tag items and turning them into a linear scale factor the player's
+//! volume element can apply.
+
+use lofty::prelude::*;
+
+/// Which ReplayGain tag [`ReplayGain::scale_factor`] should use, backed by
+/// the "replaygain-mode" setting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GainMode {
+    /// Play back at the tagged loudness, unchanged.
+    Off,
+    /// Normalize to the track's own loudness.
+    Track,
+    /// Normalize to the album's loudness, preserving the relative levels
+    /// between tracks on the same album.
+    Album,
+}
+
+impl GainMode {
+    /// Parse the "replaygain-mode" GSettings string, defaulting to `Track`
+    /// for anything unrecognized so a stale or hand-edited setting doesn't
+    /// silently disable normalization.
+    pub fn from_settings_str(s: &str) -> Self {
+        match s {
+            "off" => Self::Off,
+            "album" => Self::Album,
+            _ => Self::Track,
+        }
+    }
+}
+
+/// Track- and album-level ReplayGain data for a song, in decibels/linear-peak
+/// as stored in the tag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayGain {
+    pub gain_db: f64,
+    pub peak: Option<f64>,
+    pub album_gain_db: Option<f64>,
+    pub album_peak: Option<f64>,
+}
+
+impl ReplayGain {
+    /// Read the `REPLAYGAIN_TRACK_GAIN`/`REPLAYGAIN_TRACK_PEAK` and
+    /// `REPLAYGAIN_ALBUM_GAIN`/`REPLAYGAIN_ALBUM_PEAK` items from a tag.
+    /// Track gain is required for this to return anything at all; album
+    /// gain is optional, since plenty of rips only carry track tags.
+    pub fn from_tag(tag: &lofty::tag::Tag) -> Option<Self> {
+        let gain_db = tag
+            .get_string(&lofty::tag::ItemKey::ReplayGainTrackGain)
+            .and_then(parse_db)?;
+
+        let peak = tag
+            .get_string(&lofty::tag::ItemKey::ReplayGainTrackPeak)
+            .and_then(|s| s.trim().parse::<f64>().ok());
+
+        let album_gain_db = tag
+            .get_string(&lofty::tag::ItemKey::ReplayGainAlbumGain)
+            .and_then(parse_db);
+
+        let album_peak = tag
+            .get_string(&lofty::tag::ItemKey::ReplayGainAlbumPeak)
+            .and_then(|s| s.trim().parse::<f64>().ok());
+
+        Some(Self {
+            gain_db,
+            peak,
+            album_gain_db,
+            album_peak,
+        })
+    }
+
+    /// Linear volume multiplier to apply on top of the user's chosen
+    /// volume, clamped so a clipping-prone peak value never pushes the
+    /// scaled signal above 1.0 and a single bad tag can't blast the output
+    /// far louder than normal. Falls back to the track values when `mode`
+    /// is `Album` but the tag has no album gain.
+    pub fn scale_factor(&self, mode: GainMode) -> f64 {
+        let (gain_db, peak) = match mode {
+            GainMode::Off => return 1.0,
+            GainMode::Track => (self.gain_db, self.peak),
+            GainMode::Album => match self.album_gain_db {
+                Some(gain_db) => (gain_db, self.album_peak),
+                None => (self.gain_db, self.peak),
+            },
+        };
+
+        let mut factor = 10f64.powf(gain_db / 20.0).clamp(0.1, 3.0);
+
+        if let Some(peak) = peak {
+            if peak > 0.0 {
+                factor = factor.min(1.0 / peak);
+            }
+        }
+
+        factor
+    }
+}
+
+fn parse_db(s: &str) -> Option<f64> {
+    s.trim().trim_end_matches("dB").trim().parse().ok()
+}
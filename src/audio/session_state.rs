@@ -0,0 +1,143 @@
+// SPDX-FileCopyrightText: 2024  Emmanuele Bassi
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Persisting and restoring the playback session (queue, current track,
+//! position, repeat mode and volume) across restarts, so closing Amberol
+//! mid-album doesn't lose your place.
+
+use std::path::PathBuf;
+
+use gtk::gio;
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::audio::RepeatMode;
+
+/// Everything needed to resume playback exactly where the user left off.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionState {
+    pub queue_uris: Vec<String>,
+    pub current_index: Option<usize>,
+    pub position_secs: u64,
+    pub repeat_mode: SerializableRepeatMode,
+    pub volume: f64,
+}
+
+/// `RepeatMode` doesn't derive `Serialize`/`Deserialize` (it lives in a
+/// module with no `serde` dependency), so mirror it here for storage.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum SerializableRepeatMode {
+    Consecutive,
+    RepeatAll,
+    RepeatOne,
+}
+
+impl From<RepeatMode> for SerializableRepeatMode {
+    fn from(mode: RepeatMode) -> Self {
+        match mode {
+            RepeatMode::Consecutive => Self::Consecutive,
+            RepeatMode::RepeatAll => Self::RepeatAll,
+            RepeatMode::RepeatOne => Self::RepeatOne,
+        }
+    }
+}
+
+impl From<SerializableRepeatMode> for RepeatMode {
+    fn from(mode: SerializableRepeatMode) -> Self {
+        match mode {
+            SerializableRepeatMode::Consecutive => Self::Consecutive,
+            SerializableRepeatMode::RepeatAll => Self::RepeatAll,
+            SerializableRepeatMode::RepeatOne => Self::RepeatOne,
+        }
+    }
+}
+
+fn state_path() -> PathBuf {
+    glib::user_data_dir().join("amberol").join("session.json")
+}
+
+/// Write the session state to disk, overwriting any previous one.
+///
+/// Writes to a `.tmp` sibling first and renames it into place, so a crash
+/// or power loss mid-write can never leave a truncated/corrupt
+/// `session.json` behind for the next launch to choke on.
+pub fn save(state: &SessionState) {
+    let path = state_path();
+
+    if let Some(parent) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            warn!("Could not create session state directory {parent:?}: {e}");
+            return;
+        }
+    }
+
+    let json = match serde_json::to_string_pretty(state) {
+        Ok(json) => json,
+        Err(e) => {
+            warn!("Could not serialize session state: {e}");
+            return;
+        }
+    };
+
+    let tmp_path = path.with_extension("json.tmp");
+    if let Err(e) = std::fs::write(&tmp_path, json) {
+        warn!("Could not write session state to {tmp_path:?}: {e}");
+        return;
+    }
+
+    if let Err(e) = std::fs::rename(&tmp_path, &path) {
+        warn!("Could not replace session state at {path:?}: {e}");
+        return;
+    }
+
+    debug!("Saved session state to {path:?}");
+}
+
+/// Load the last saved session state, if any, dropping queue entries whose
+/// file no longer exists and shifting `current_index` back by the number of
+/// entries dropped before it (`None` if that lands past the end of the
+/// remaining queue). The saved position is returned unchanged; see
+/// [`clamp_position`] for bounding it to a track's duration.
+pub fn load() -> Option<SessionState> {
+    let path = state_path();
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| debug!("No previous session state at {path:?}: {e}"))
+        .ok()?;
+
+    let mut state: SessionState = serde_json::from_str(&contents)
+        .map_err(|e| warn!("Could not parse session state {path:?}: {e}"))
+        .ok()?;
+
+    let original_current = state.current_index;
+    let mut dropped_before_current = 0;
+    let mut valid_uris = Vec::with_capacity(state.queue_uris.len());
+
+    for (index, uri) in state.queue_uris.into_iter().enumerate() {
+        let exists = gio::File::for_uri(&uri).path().is_some_and(|p| p.is_file());
+        if exists {
+            valid_uris.push(uri);
+        } else {
+            debug!("Dropping missing queue entry from saved session: {uri}");
+            if original_current.is_some_and(|current| index < current) {
+                dropped_before_current += 1;
+            }
+        }
+    }
+
+    state.current_index = original_current
+        .map(|current| current - dropped_before_current)
+        .filter(|&current| current < valid_uris.len());
+    state.queue_uris = valid_uris;
+
+    Some(state)
+}
+
+/// Clamp a restored position to a track's duration, so a shorter
+/// re-encode of the same file can't seek past the end of playback.
+pub fn clamp_position(position_secs: u64, duration_secs: u64) -> u64 {
+    if duration_secs == 0 {
+        position_secs
+    } else {
+        position_secs.min(duration_secs)
+    }
+}
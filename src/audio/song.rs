@@ -16,10 +16,96 @@ use once_cell::sync::Lazy;
 use sha2::{Digest, Sha256};
 
 use crate::{
-    audio::cover_cache::{CoverArt, CoverCache},
+    audio::{
+        cover_cache::{CoverArt, CoverCache},
+        lyrics::{self, Lyrics},
+        replaygain::{self, ReplayGain},
+        song_source,
+    },
     i18n::i18n,
 };
 
+/// Everything [`SongData::from_scanned`] needs from a local file's tags,
+/// minus the `gio`/`gdk` types it builds from them (a `gio::File`, and the
+/// `gdk::Texture` behind cover art) — those aren't `Send`, so unlike this
+/// struct they can't be produced by a worker thread.
+///
+/// [`crate::audio::library_scanner`]'s worker pool calls [`Self::read`] off
+/// the main thread; [`SongData::from_scanned`] then finishes the job on
+/// whichever thread actually owns the result.
+#[derive(Debug, Clone)]
+pub struct ScannedTags {
+    artist: Option<String>,
+    title: Option<String>,
+    album: Option<String>,
+    duration: u64,
+    lyrics: Option<Lyrics>,
+    /// The primary (or first readable) tag, kept around so cover art can
+    /// still be looked up later without re-reading the file a second time.
+    tag: Option<lofty::tag::Tag>,
+}
+
+impl ScannedTags {
+    /// Parse `path`'s tags. Pure computation over plain data — no GTK
+    /// types involved, so this is safe to call from any thread.
+    pub fn read(path: &Path) -> Self {
+        let tagged_file = match lofty::read_from_path(path) {
+            Ok(f) => {
+                debug!("Successfully read metadata from: {:?}", path);
+                Some(f)
+            }
+            Err(e) => {
+                warn!("Unable to read metadata from file {:?}: {} - will create basic entry", path, e);
+                None
+            }
+        };
+
+        let mut artist = None;
+        let mut title = None;
+        let mut album = None;
+        let mut tag = None;
+        let mut lyrics = None;
+
+        if let Some(ref tagged_file) = tagged_file {
+            if let Some(primary) = tagged_file.primary_tag() {
+                debug!("Found primary tag");
+                artist = primary.artist().map(|s| s.to_string());
+                title = primary.title().map(|s| s.to_string());
+                album = primary.album().map(|s| s.to_string());
+                lyrics = lyrics::from_tag(primary);
+                tag = Some(primary.clone());
+            } else {
+                warn!("Unable to load primary tag for: {:?}", path);
+                for candidate in tagged_file.tags() {
+                    debug!("Found tag: {:?}", candidate.tag_type());
+                    artist = candidate.artist().map(|s| s.to_string());
+                    title = candidate.title().map(|s| s.to_string());
+                    album = candidate.album().map(|s| s.to_string());
+                    tag = Some(candidate.clone());
+
+                    if artist.is_some() && title.is_some() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        let duration = tagged_file
+            .as_ref()
+            .map(|f| f.properties().duration().as_secs())
+            .unwrap_or(0);
+
+        ScannedTags {
+            artist,
+            title,
+            album,
+            duration,
+            lyrics,
+            tag,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct SongData {
     artist: Option<String>,
@@ -30,6 +116,16 @@ pub struct SongData {
     uuid: Option<String>,
     duration: u64,
     file: gio::File,
+    /// The URI the player should actually open, which may differ from
+    /// `file`'s URI for remote sources (e.g. a signed Jellyfin stream URL).
+    stream_uri: Option<String>,
+    lyrics: Option<Lyrics>,
+    replaygain: Option<ReplayGain>,
+    /// Start/end offsets (in milliseconds, relative to `file`) for a
+    /// virtual track cut out of a CUE sheet; `None` for an ordinary,
+    /// whole-file song.
+    cue_start_ms: Option<u64>,
+    cue_end_ms: Option<u64>,
 }
 
 impl SongData {
@@ -82,62 +178,49 @@ impl SongData {
     }
 
     pub fn from_uri(uri: &str) -> Self {
-        let now = Instant::now();
+        if uri.starts_with("http://") || uri.starts_with("https://") || uri.starts_with("jellyfin://") {
+            return Self::from_remote_uri(uri, Instant::now());
+        }
 
         let file = gio::File::for_uri(uri);
         let path = file.path().expect("Unable to find file");
+        let scanned = ScannedTags::read(&path);
+        Self::from_scanned(uri, scanned)
+    }
+
+    /// Assemble a [`SongData`] from tags [`ScannedTags::read`] already read
+    /// off disk, e.g. by [`crate::audio::library_scanner`]'s worker pool.
+    ///
+    /// This is the main-thread half of what [`Self::from_uri`] used to do
+    /// in one go: `gio::File` and the `gdk::Texture` cover art behind
+    /// [`CoverCache`] aren't `Send`, so they can only be built here, not in
+    /// the worker that produced `scanned`.
+    pub fn from_scanned(uri: &str, scanned: ScannedTags) -> Self {
+        let now = Instant::now();
 
-        let tagged_file = match lofty::read_from_path(&path) {
-            Ok(f) => {
-                debug!("Successfully read metadata from: {:?}", path);
-                Some(f)
-            },
-            Err(e) => {
-                warn!("Unable to read metadata from file {:?}: {} - will create basic entry", path, e);
-                // Still try to create a basic song entry without metadata
-                None
-            }
-        };
+        let file = gio::File::for_uri(uri);
+        let path = file.path().expect("Unable to find file");
 
-        let mut cover_cache = CoverCache::global().lock().unwrap();
+        let ScannedTags {
+            mut artist,
+            mut title,
+            album,
+            duration,
+            tag,
+            mut lyrics,
+        } = scanned;
 
-        let mut artist = None;
-        let mut title = None;
-        let mut album = None;
         let mut cover_art = None;
         let mut cover_uuid = None;
-        
-        if let Some(ref tagged_file) = tagged_file {
-            if let Some(tag) = tagged_file.primary_tag() {
-            debug!("Found primary tag");
-            artist = tag.artist().map(|s| s.to_string());
-            title = tag.title().map(|s| s.to_string());
-            album = tag.album().map(|s| s.to_string());
-                if let Some(res) = cover_cache.cover_art(&path, tag) {
-                    cover_art = Some(res.0);
-                    cover_uuid = Some(res.1);
-                }
-            } else {
-                warn!("Unable to load primary tag for: {}", uri);
-                for tag in tagged_file.tags() {
-                debug!("Found tag: {:?}", tag.tag_type());
-                artist = tag.artist().map(|s| s.to_string());
-                title = tag.title().map(|s| s.to_string());
-                album = tag.album().map(|s| s.to_string());
-                if let Some(res) = cover_cache.cover_art(&path, tag) {
-                    cover_art = Some(res.0);
-                    cover_uuid = Some(res.1);
-                }
-
-                    if artist.is_some() && title.is_some() {
-                        break;
-                    }
-                }
+        if let Some(ref tag) = tag {
+            let mut cover_cache = CoverCache::global().lock().unwrap();
+            if let Some(res) = cover_cache.cover_art(&path, tag) {
+                cover_art = Some(res.0);
+                cover_uuid = Some(res.1);
             }
         }
-        
-        // If we couldn't parse metadata, try to extract basic info from filename
-        if tagged_file.is_none() {
+
+        if tag.is_none() && artist.is_none() && title.is_none() {
             warn!("No metadata available, using filename for basic info");
             if let Some(filename) = path.file_stem().and_then(|s| s.to_str()) {
                 // Try to extract artist - title from filename patterns like "Artist - Title.mp3"
@@ -151,6 +234,10 @@ impl SongData {
             }
         }
 
+        if lyrics.is_none() {
+            lyrics = lyrics::from_sidecar(&path);
+        }
+
         let uuid = match file.query_info(
             "standard::display-name",
             gio::FileQueryInfoFlags::NONE,
@@ -176,14 +263,6 @@ impl SongData {
             _ => None,
         };
 
-        let duration = if let Some(ref tagged_file) = tagged_file {
-            let properties = tagged_file.properties();
-            properties.duration().as_secs()
-        } else {
-            // Default duration when metadata parsing failed
-            0
-        };
-
         debug!(
             "Song {:?} ('{:?}') loading time: {} ms",
             &uuid,
@@ -200,16 +279,105 @@ impl SongData {
             uuid,
             duration,
             file,
+            stream_uri: None,
+            lyrics,
+            replaygain: tag.as_ref().and_then(ReplayGain::from_tag),
+        }
+    }
+
+    /// Resolve a non-local (HTTP or Jellyfin) URI via [`song_source`] instead
+    /// of reading tags off a local path. Falls back to a bare entry built
+    /// from the URI itself if the backend cannot be reached.
+    fn from_remote_uri(uri: &str, now: Instant) -> Self {
+        let file = gio::File::for_uri(uri);
+
+        let Some(metadata) = song_source::resolve(uri) else {
+            warn!("Unable to resolve remote song source for {uri}, using bare entry");
+            return SongData {
+                artist: None,
+                title: Some(uri.to_string()),
+                album: None,
+                cover_art: None,
+                cover_uuid: None,
+                uuid: Some(format!("{:x}", Sha256::digest(uri.as_bytes()))),
+                duration: 0,
+                file,
+                stream_uri: Some(uri.to_string()),
+                lyrics: None,
+                replaygain: None,
+            };
+        };
+
+        let mut cover_art = None;
+        let mut cover_uuid = None;
+        if let Some(art) = metadata.cover_art {
+            cover_uuid = Some(format!("{:x}", Sha256::digest(uri.as_bytes())));
+            cover_art = Some(art);
+        }
+
+        let mut hasher = Sha256::new();
+        hasher.update(uri.as_bytes());
+        if let Some(ref artist) = metadata.artist {
+            hasher.update(artist);
+        }
+        if let Some(ref title) = metadata.title {
+            hasher.update(title);
+        }
+        let uuid = Some(format!("{:x}", hasher.finalize()));
+
+        debug!(
+            "Remote song {:?} ('{:?}') loading time: {} ms",
+            &uuid,
+            &metadata.title,
+            now.elapsed().as_millis()
+        );
+
+        SongData {
+            artist: metadata.artist,
+            title: metadata.title,
+            album: metadata.album,
+            cover_art,
+            cover_uuid,
+            uuid,
+            duration: metadata.duration,
+            file,
+            stream_uri: Some(metadata.stream_uri),
+            lyrics: None,
+            // Remote sources don't expose ReplayGain tags through
+            // `SourceMetadata` today.
+            replaygain: None,
         }
     }
 
     pub fn uri(&self) -> String {
-        self.file.uri().to_string()
+        self.stream_uri.clone().unwrap_or_else(|| self.file.uri().to_string())
     }
 
     pub fn file(&self) -> gio::File {
         self.file.clone()
     }
+
+    pub fn lyrics(&self) -> Option<&Lyrics> {
+        self.lyrics.as_ref()
+    }
+
+    /// Linear volume multiplier from this song's ReplayGain tag under the
+    /// given `mode`, or `1.0` (no change) if it has none.
+    pub fn volume_scale(&self, mode: replaygain::GainMode) -> f64 {
+        self.replaygain.map(|rg| rg.scale_factor(mode)).unwrap_or(1.0)
+    }
+
+    /// Start offset into `file` this track should begin playing from, in
+    /// milliseconds, or `None` for an ordinary, whole-file song.
+    pub fn cue_start_ms(&self) -> Option<u64> {
+        self.cue_start_ms
+    }
+
+    /// Offset into `file` this track should stop playing at, in
+    /// milliseconds, or `None` if it plays to the end of `file`.
+    pub fn cue_end_ms(&self) -> Option<u64> {
+        self.cue_end_ms
+    }
 }
 
 impl Default for SongData {
@@ -223,6 +391,11 @@ impl Default for SongData {
             uuid: None,
             duration: 0,
             file: gio::File::for_path("/does-not-exist"),
+            stream_uri: None,
+            lyrics: None,
+            replaygain: None,
+            cue_start_ms: None,
+            cue_end_ms: None,
         }
     }
 }
@@ -322,6 +495,19 @@ impl Song {
         }
     }
 
+    /// Build a [`Song`] from tags a worker thread already read with
+    /// [`ScannedTags::read`], instead of going through the "uri" property
+    /// setter and re-parsing them on whichever thread calls this.
+    pub fn from_scanned(uri: &str, scanned: ScannedTags) -> Result<Song, &'static str> {
+        let res = Self::empty();
+        res.imp().data.replace(SongData::from_scanned(uri, scanned));
+        if res.equals(&Song::default()) {
+            Err("Invalid song")
+        } else {
+            Ok(res)
+        }
+    }
+
     pub fn empty() -> Self {
         glib::Object::new()
     }
@@ -416,6 +602,49 @@ impl Song {
     pub fn file(&self) -> gio::File {
         self.imp().data.borrow().file()
     }
+
+    pub fn lyrics(&self) -> Option<Lyrics> {
+        self.imp().data.borrow().lyrics().cloned()
+    }
+
+    pub fn volume_scale(&self, mode: replaygain::GainMode) -> f64 {
+        self.imp().data.borrow().volume_scale(mode)
+    }
+
+    pub fn cue_start_ms(&self) -> Option<u64> {
+        self.imp().data.borrow().cue_start_ms()
+    }
+
+    pub fn cue_end_ms(&self) -> Option<u64> {
+        self.imp().data.borrow().cue_end_ms()
+    }
+
+    /// Construct a virtual per-track `Song` cut out of a CUE sheet entry:
+    /// the tags/cover art come from `file_uri` itself, but the title and
+    /// performer are overridden from the sheet when present, and playback
+    /// is clamped to the half-open range starting at `track.start_ms` and
+    /// ending at `end_ms`.
+    pub fn from_cue_track(
+        file_uri: &str,
+        track: &crate::audio::cue::CueTrack,
+        end_ms: Option<u64>,
+    ) -> Result<Song, &'static str> {
+        let song = Song::from_uri(file_uri)?;
+
+        {
+            let mut data = song.imp().data.borrow_mut();
+            if let Some(title) = &track.title {
+                data.title = Some(title.clone());
+            }
+            if let Some(performer) = &track.performer {
+                data.artist = Some(performer.clone());
+            }
+            data.cue_start_ms = Some(track.start_ms);
+            data.cue_end_ms = end_ms;
+        }
+
+        Ok(song)
+    }
 }
 
 impl Default for Song {
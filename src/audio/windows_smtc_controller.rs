@@ -0,0 +1,175 @@
+// SPDX-FileCopyrightText: 2024  Emmanuele Bassi
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Windows `SystemMediaTransportControls` integration, giving Amberol the
+//! same "now playing" entry in the volume flyout and media keys support
+//! that [`crate::audio::mpris_controller::MprisController`] provides on
+//! Linux/FreeBSD through MPRIS.
+
+use std::cell::RefCell;
+
+use async_channel::Sender;
+use log::error;
+
+#[cfg(target_os = "windows")]
+use windows::{
+    core::HSTRING,
+    Media::{
+        MediaPlaybackStatus, MediaPlaybackType, SystemMediaTransportControls,
+        SystemMediaTransportControlsButton, SystemMediaTransportControlsButtonPressedEventArgs,
+    },
+    Win32::Media::{ISystemMediaTransportControlsInterop, SystemMediaTransportControlsInterop},
+    Win32::System::WinRT::RoInitialize,
+    Win32::System::WinRT::RO_INIT_SINGLETHREADED,
+};
+
+use crate::audio::{Controller, PlaybackAction, PlaybackState, RepeatMode, Song};
+
+#[derive(Debug)]
+#[allow(dead_code)]
+pub struct WindowsSmtcController {
+    #[cfg(target_os = "windows")]
+    smtc: Option<SystemMediaTransportControls>,
+    song: RefCell<Option<Song>>,
+}
+
+#[allow(dead_code)]
+impl WindowsSmtcController {
+    pub fn new(sender: Sender<PlaybackAction>, hwnd: isize) -> Self {
+        #[cfg(target_os = "windows")]
+        {
+            let smtc = Self::create_smtc(hwnd)
+                .map_err(|e| error!("Failed to create SystemMediaTransportControls: {e}"))
+                .ok();
+
+            if let Some(smtc) = &smtc {
+                setup_button_handler(sender, smtc);
+            }
+
+            Self {
+                smtc,
+                song: RefCell::new(None),
+            }
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = (sender, hwnd);
+            Self {
+                song: RefCell::new(None),
+            }
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn create_smtc(hwnd: isize) -> windows::core::Result<SystemMediaTransportControls> {
+        unsafe {
+            RoInitialize(RO_INIT_SINGLETHREADED).ok();
+
+            let interop: ISystemMediaTransportControlsInterop =
+                windows::core::factory::<SystemMediaTransportControlsInterop, _>()?;
+            let smtc = interop.GetForWindow(windows::Win32::Foundation::HWND(hwnd as _))?;
+
+            smtc.SetIsEnabled(true)?;
+            smtc.SetIsPlayEnabled(true)?;
+            smtc.SetIsPauseEnabled(true)?;
+            smtc.SetIsNextEnabled(true)?;
+            smtc.SetIsPreviousEnabled(true)?;
+
+            Ok(smtc)
+        }
+    }
+
+    pub fn update_song(&self, song: &Song) {
+        self.song.replace(Some(song.clone()));
+
+        #[cfg(target_os = "windows")]
+        {
+            let Some(smtc) = &self.smtc else { return };
+
+            let result: windows::core::Result<()> = (|| {
+                let updater = smtc.DisplayUpdater()?;
+                updater.SetType(MediaPlaybackType::Music)?;
+
+                let props = updater.MusicProperties()?;
+                props.SetTitle(&HSTRING::from(song.title().as_str()))?;
+                props.SetArtist(&HSTRING::from(song.artist().as_str()))?;
+                props.SetAlbumTitle(&HSTRING::from(song.album().as_str()))?;
+
+                updater.Update()?;
+                Ok(())
+            })();
+
+            if let Err(e) = result {
+                error!("Could not update SMTC metadata: {e}");
+            }
+        }
+    }
+}
+
+impl Controller for WindowsSmtcController {
+    fn set_playback_state(&self, state: &PlaybackState) {
+        #[cfg(target_os = "windows")]
+        {
+            let Some(smtc) = &self.smtc else { return };
+
+            let status = match state {
+                PlaybackState::Playing => MediaPlaybackStatus::Playing,
+                PlaybackState::Paused => MediaPlaybackStatus::Paused,
+                _ => MediaPlaybackStatus::Stopped,
+            };
+
+            if let Err(e) = smtc.SetPlaybackStatus(status) {
+                error!("Could not update SMTC playback state: {e}");
+            }
+        }
+
+        #[cfg(not(target_os = "windows"))]
+        {
+            let _ = state;
+        }
+    }
+
+    fn set_song(&self, song: &Song) {
+        self.update_song(song);
+    }
+
+    fn set_position(&self, _position: u64) {
+        // Position updates are dropped: only metadata and playback status
+        // are forwarded to SMTC, so the flyout's scrubber stays unset.
+    }
+
+    fn set_repeat_mode(&self, _repeat: RepeatMode) {
+        // SMTC has no loop-status concept exposed to the OS "now playing"
+        // flyout, unlike MPRIS' `LoopStatus`; nothing to forward here.
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn setup_button_handler(sender: Sender<PlaybackAction>, smtc: &SystemMediaTransportControls) {
+    let handler = windows::Foundation::TypedEventHandler::<
+        SystemMediaTransportControls,
+        SystemMediaTransportControlsButtonPressedEventArgs,
+    >::new(move |_sender, args| {
+        let Some(args) = args else { return Ok(()) };
+
+        let action = match args.Button()? {
+            SystemMediaTransportControlsButton::Play => Some(PlaybackAction::Play),
+            SystemMediaTransportControlsButton::Pause => Some(PlaybackAction::Pause),
+            SystemMediaTransportControlsButton::Next => Some(PlaybackAction::Skip),
+            SystemMediaTransportControlsButton::Previous => Some(PlaybackAction::Previous),
+            SystemMediaTransportControlsButton::Stop => Some(PlaybackAction::Stop),
+            _ => None,
+        };
+
+        if let Some(action) = action {
+            sender.send_blocking(action).unwrap();
+        }
+
+        Ok(())
+    });
+
+    if let Err(e) = smtc.ButtonPressed(&handler) {
+        error!("Could not register SMTC button handler: {e}");
+    }
+}
@@ -0,0 +1,127 @@
+// SPDX-FileCopyrightText: 2024  Emmanuele Bassi
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Import and export of [XSPF](https://www.xspf.org/spec) playlists, so
+//! queues built in Amberol can be shared with (or loaded from) other
+//! players.
+
+use std::path::Path;
+
+use log::warn;
+
+/// A single track entry read out of (or about to be written to) an XSPF
+/// playlist. Only the fields Amberol actually uses are kept; anything else
+/// in a `<track>` element is ignored on import and omitted on export.
+#[derive(Debug, Clone)]
+pub struct XspfTrack {
+    pub location: String,
+    pub title: Option<String>,
+    pub creator: Option<String>,
+    pub album: Option<String>,
+}
+
+/// Parse an XSPF document into an ordered list of tracks.
+pub fn parse(contents: &str) -> Vec<XspfTrack> {
+    let mut tracks = Vec::new();
+
+    for track_xml in split_elements(contents, "track") {
+        let Some(location) = extract_element(&track_xml, "location") else {
+            warn!("Skipping XSPF <track> with no <location>");
+            continue;
+        };
+
+        tracks.push(XspfTrack {
+            location: unescape(&location),
+            title: extract_element(&track_xml, "title").map(|s| unescape(&s)),
+            creator: extract_element(&track_xml, "creator").map(|s| unescape(&s)),
+            album: extract_element(&track_xml, "album").map(|s| unescape(&s)),
+        });
+    }
+
+    tracks
+}
+
+/// Parse an XSPF playlist from disk.
+pub fn parse_file(path: &Path) -> Option<Vec<XspfTrack>> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| warn!("Could not read XSPF playlist {path:?}: {e}"))
+        .ok()?;
+    Some(parse(&contents))
+}
+
+/// Serialize a queue into an XSPF playlist document.
+pub fn write(tracks: &[XspfTrack]) -> String {
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n");
+    out.push_str("  <trackList>\n");
+
+    for track in tracks {
+        out.push_str("    <track>\n");
+        out.push_str(&format!(
+            "      <location>{}</location>\n",
+            escape(&track.location)
+        ));
+        if let Some(title) = &track.title {
+            out.push_str(&format!("      <title>{}</title>\n", escape(title)));
+        }
+        if let Some(creator) = &track.creator {
+            out.push_str(&format!("      <creator>{}</creator>\n", escape(creator)));
+        }
+        if let Some(album) = &track.album {
+            out.push_str(&format!("      <album>{}</album>\n", escape(album)));
+        }
+        out.push_str("    </track>\n");
+    }
+
+    out.push_str("  </trackList>\n");
+    out.push_str("</playlist>\n");
+    out
+}
+
+/// Serialize a queue into an XSPF playlist and write it to disk.
+pub fn write_file(path: &Path, tracks: &[XspfTrack]) -> std::io::Result<()> {
+    std::fs::write(path, write(tracks))
+}
+
+/// Split out the inner XML of every top-level `<name>...</name>` element.
+/// This is a deliberately small, allocation-happy parser rather than a full
+/// XML implementation, since XSPF playlists written by Amberol (and the
+/// handful of other players we interoperate with) never nest same-named
+/// elements inside a `<track>`.
+fn split_elements(xml: &str, name: &str) -> Vec<String> {
+    let open = format!("<{name}>");
+    let close = format!("</{name}>");
+    let mut out = Vec::new();
+    let mut rest = xml;
+
+    while let Some(start) = rest.find(&open) {
+        let after_open = &rest[start + open.len()..];
+        let Some(end) = after_open.find(&close) else {
+            break;
+        };
+        out.push(after_open[..end].to_string());
+        rest = &after_open[end + close.len()..];
+    }
+
+    out
+}
+
+fn extract_element(xml: &str, name: &str) -> Option<String> {
+    split_elements(xml, name).into_iter().next()
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
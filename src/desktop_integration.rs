@@ -101,20 +101,34 @@ impl DesktopIntegration {
     
     /// Setup system tray integration
     fn setup_system_tray(_app: &crate::application::Application) {
-        info!("🔔 Setting up system tray integration");
-        
-        #[cfg(target_os = "windows")]
-        {
-            // System tray is handled in the separate system_tray module
-            info!("🪟 Windows system tray will use custom icon");
-        }
-        
-        #[cfg(not(target_os = "windows"))]
-        {
-            info!("🐧 System tray integration not implemented for this platform");
-        }
+        // The tray itself is created in `imp::Application::startup`, via the
+        // platform-abstracted `system_tray::SystemTray` (a native
+        // StatusNotifierItem on Linux/FreeBSD, Shell_NotifyIcon on Windows).
+        info!("🔔 System tray is set up by Application::startup");
     }
     
+    /// Resolve `name` to an on-disk icon file through the freedesktop icon
+    /// theme spec, at the closest available size to `size`. This walks the
+    /// user's actual configured theme (and its parents) via
+    /// [`gtk::IconTheme`], rather than the hand-rolled temp-dir overrides
+    /// used by `icon_theme_provider` and `icon_hijacker`, so it also finds icons we never
+    /// generated ourselves.
+    pub fn resolve_icon(name: &str, size: i32) -> Option<std::path::PathBuf> {
+        let display = gtk::gdk::Display::default()?;
+        let icon_theme = gtk::IconTheme::for_display(&display);
+
+        let paintable = icon_theme.lookup_icon(
+            name,
+            &[],
+            size,
+            1,
+            gtk::TextDirection::None,
+            gtk::IconLookupFlags::empty(),
+        );
+
+        paintable.file().and_then(|file| file.path())
+    }
+
     /// Create application icon at multiple sizes for desktop files
     pub fn generate_desktop_icons() -> Result<(), Box<dyn std::error::Error>> {
         info!("🖥️ Generating desktop icons");
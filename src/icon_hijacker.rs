@@ -5,12 +5,67 @@
 
 use gtk::{gdk, glib, prelude::*};
 use log::{info, warn};
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::rc::Rc;
 use std::sync::{Arc, Mutex};
 
+use crate::audio::{Controller, PlaybackState, RepeatMode, Song};
+
+/// An RGBA color as the `(red, green, blue, alpha)` tuple `cairo::Context`
+/// methods like `set_source_rgba` take.
+type Rgba = (f64, f64, f64, f64);
+
+/// The `-gtk-icon-palette` slots GTK's own symbolic-icon renderer recolors
+/// against, resolved from a widget's active theme/accent so our
+/// hand-drawn fallback glyphs track light/dark and accent changes the
+/// same way real symbolic icons do.
+struct IconPalette {
+    foreground: Rgba,
+    error: Rgba,
+    warning: Rgba,
+}
+
+impl IconPalette {
+    /// Resolve the palette from `widget`'s style context, falling back to
+    /// Adwaita's own defaults for any slot the active theme doesn't define.
+    fn resolve(widget: &gtk::Widget) -> Self {
+        let style_context = widget.style_context();
+        let lookup = |name: &str, fallback: Rgba| -> Rgba {
+            style_context
+                .lookup_color(name)
+                .map(|c| (c.red() as f64, c.green() as f64, c.blue() as f64, c.alpha() as f64))
+                .unwrap_or(fallback)
+        };
+
+        Self {
+            foreground: lookup("theme_fg_color", (0.18, 0.20, 0.21, 1.0)),
+            error: lookup("error_color", (0.75, 0.13, 0.13, 1.0)),
+            warning: lookup("warning_color", (0.75, 0.48, 0.0, 1.0)),
+        }
+    }
+
+    /// The palette as it would resolve with no widget tree available yet,
+    /// e.g. the very first render before any window exists.
+    fn fallback() -> Self {
+        Self {
+            foreground: (0.18, 0.20, 0.21, 1.0),
+            error: (0.75, 0.13, 0.13, 1.0),
+            warning: (0.75, 0.48, 0.0, 1.0),
+        }
+    }
+}
+
 /// Aggressive icon hijacker that intercepts and overrides all icon operations
 pub struct IconHijacker {
     replacement_textures: Arc<Mutex<HashMap<String, gdk::Texture>>>,
+    /// Cover textures already cropped to a rounded square, keyed by album,
+    /// so repeated tracks from the same release don't get re-rendered.
+    cover_cache: RefCell<HashMap<String, gdk::Texture>>,
+    /// Windows hooked by [`Self::hook_window_creation`], so a track change
+    /// can re-apply the now-current app-id texture without waiting for the
+    /// next window to open.
+    windows: RefCell<Vec<glib::WeakRef<gtk::Window>>>,
 }
 
 impl IconHijacker {
@@ -18,46 +73,63 @@ impl IconHijacker {
     pub fn new() -> Self {
         Self {
             replacement_textures: Arc::new(Mutex::new(HashMap::new())),
+            cover_cache: RefCell::new(HashMap::new()),
+            windows: RefCell::new(Vec::new()),
         }
     }
     
     /// Start the aggressive icon hijacking system
     pub fn start_hijacking() {
         info!("🚨 Starting AGGRESSIVE icon hijacking system");
-        
-        let hijacker = Self::new();
-        
+
+        let hijacker = Rc::new(Self::new());
+
         // Create all replacement textures immediately
         hijacker.create_replacement_textures();
-        
-        // Start the continuous hijacking loop
-        hijacker.start_continuous_hijacking();
-        
-        // Hook into window creation
+
+        // Register them under an icon theme search path once, so stock
+        // names like `web-browser-symbolic` resolve to our art through
+        // GTK's own `IconTheme` lookup wherever they're requested, instead
+        // of a 500ms timer re-walking every window's widget tree to swap
+        // `GtkImage`/`GtkButton` paintables by hand.
+        hijacker.install_replacement_icon_theme();
+
+        // Hook into window creation, to force the actual window icon name
+        // (which isn't picked up by theme resolution alone)
         hijacker.hook_window_creation();
-        
-        // Hook into about dialog creation specifically
-        hijacker.hook_about_dialog_creation();
-        
+
+        // Re-render the symbolic icons whenever the color scheme changes,
+        // so they track light/dark and theme swaps like real symbolic
+        // icons do instead of staying fixed at startup's colors.
+        hijacker.watch_theme_changes();
+
         info!("🚨 Icon hijacking system ACTIVE");
     }
     
-    /// Create all replacement textures upfront
+    /// Create all replacement textures upfront, recoloring the symbolic
+    /// ones (everything except the full-color app icon) against the
+    /// current theme's palette.
     fn create_replacement_textures(&self) {
+        let palette = gtk::gio::Application::default()
+            .and_then(|app| app.downcast_ref::<gtk::Application>().cloned())
+            .and_then(|app| app.windows().first().cloned())
+            .map(|window| IconPalette::resolve(window.upcast_ref::<gtk::Widget>()))
+            .unwrap_or_else(IconPalette::fallback);
+
         let icons_to_create = vec![
             ("io.bassi.Amberol", Self::create_app_icon_texture()),
             ("io.bassi.Amberol.Devel", Self::create_app_icon_texture()),
-            ("web-browser-symbolic", Self::create_web_icon_texture()),
-            ("user-home-symbolic", Self::create_web_icon_texture()),
-            ("bug-symbolic", Self::create_bug_icon_texture()),
-            ("document-edit-symbolic", Self::create_bug_icon_texture()),
-            ("system-search-symbolic", Self::create_search_icon_texture()),
-            ("open-menu-symbolic", Self::create_menu_icon_texture()),
+            ("web-browser-symbolic", Self::create_web_icon_texture(&palette)),
+            ("user-home-symbolic", Self::create_web_icon_texture(&palette)),
+            ("bug-symbolic", Self::create_bug_icon_texture(&palette)),
+            ("document-edit-symbolic", Self::create_bug_icon_texture(&palette)),
+            ("system-search-symbolic", Self::create_search_icon_texture(&palette)),
+            ("open-menu-symbolic", Self::create_menu_icon_texture(&palette)),
             ("audio-only-symbolic", Self::create_audio_icon_texture()),
-            ("folder-music-symbolic", Self::create_folder_icon_texture()),
+            ("folder-music-symbolic", Self::create_folder_icon_texture(&palette)),
             ("image-missing", Self::create_app_icon_texture()),
         ];
-        
+
         let mut textures = self.replacement_textures.lock().unwrap();
         for (name, texture_opt) in icons_to_create {
             if let Some(texture) = texture_opt {
@@ -67,37 +139,77 @@ impl IconHijacker {
         }
         info!("🎨 Created {} replacement textures", textures.len());
     }
-    
-    /// Start continuous hijacking - runs every 500ms
-    fn start_continuous_hijacking(&self) {
-        let textures = self.replacement_textures.clone();
-        
-        glib::timeout_add_local(std::time::Duration::from_millis(500), move || {
-            Self::hijack_all_windows(&textures);
-            glib::ControlFlow::Continue
-        });
+
+    /// Re-render every symbolic texture against the current theme's
+    /// palette and re-install them, so a light/dark switch or accent
+    /// change shows up without restarting Amberol. Wired from
+    /// `AdwStyleManager::dark` and `GtkSettings::gtk-theme-name` change
+    /// notifications; see [`Self::watch_theme_changes`].
+    fn refresh_colors(&self) {
+        info!("🎨 Theme/color-scheme changed, re-rendering symbolic icons");
+        self.create_replacement_textures();
+        self.install_replacement_icon_theme();
     }
-    
-    /// Hijack all windows and their widgets
-    fn hijack_all_windows(textures: &Arc<Mutex<HashMap<String, gdk::Texture>>>) {
-        if let Some(app) = gtk::gio::Application::default() {
-            if let Some(gtk_app) = app.downcast_ref::<gtk::Application>() {
-                for window in gtk_app.windows() {
-                    Self::hijack_window_icons(&window, textures);
-                }
-            }
+
+    /// Watch for the color-scheme changes that would make
+    /// [`Self::refresh_colors`] necessary: a light/dark switch via
+    /// libadwaita, or the active GTK theme itself changing.
+    fn watch_theme_changes(self: &Rc<Self>) {
+        adw::StyleManager::default().connect_notify_local(
+            Some("dark"),
+            glib::clone!(@weak self as this => move |_, _| this.refresh_colors()),
+        );
+
+        if let Some(display) = gdk::Display::default() {
+            let settings = gtk::Settings::for_display(&display);
+            settings.connect_notify_local(
+                Some("gtk-theme-name"),
+                glib::clone!(@weak self as this => move |_, _| this.refresh_colors()),
+            );
         }
     }
     
-    /// Hijack all icons in a specific window
-    fn hijack_window_icons(window: &gtk::Window, textures: &Arc<Mutex<HashMap<String, gdk::Texture>>>) {
-        // Force set window icon
-        Self::force_set_window_icon(window, textures);
-        
-        // Hijack all widgets in the window
-        Self::hijack_widget_tree(window.upcast_ref::<gtk::Widget>(), textures);
+    /// Write every `create_replacement_textures` PNG once into a dedicated
+    /// directory and register it as an `IconTheme` search path, so any
+    /// `GtkImage`/`GtkButton` already showing one of our shadowed stock
+    /// names (see [`Self::create_replacement_textures`]) picks up our art
+    /// the next time GTK resolves it — no widget-tree walk required.
+    ///
+    /// A real `org.gtk.GResource` bundle would normally back a "named icon
+    /// provider" like this, but `gio::Resource::from_data` expects an
+    /// already-compiled gvdb blob, and this tree has no
+    /// `glib-compile-resources` build step to produce one from textures
+    /// that are only known at runtime. A filesystem search path gets GTK's
+    /// `IconTheme` the same native by-name resolution with the tools this
+    /// tree actually has.
+    fn install_replacement_icon_theme(&self) {
+        let Some(display) = gdk::Display::default() else {
+            return;
+        };
+        let icon_theme = gtk::IconTheme::for_display(&display);
+
+        let Ok(temp_dir) = std::env::temp_dir().canonicalize() else {
+            return;
+        };
+        let icons_dir = temp_dir.join("amberol-hijack-icons");
+        if std::fs::create_dir_all(&icons_dir).is_err() {
+            warn!("⚠️ Could not create replacement icon directory at {icons_dir:?}");
+            return;
+        }
+
+        let textures = self.replacement_textures.lock().unwrap();
+        for (name, texture) in textures.iter() {
+            let png_path = icons_dir.join(format!("{name}.png"));
+            if let Err(e) = texture.save_to_png(&png_path) {
+                warn!("⚠️ Failed to write replacement icon {name}: {e}");
+            }
+        }
+        drop(textures);
+
+        icon_theme.add_search_path(&icons_dir);
+        info!("🚨 Registered replacement icons via IconTheme search path: {icons_dir:?}");
     }
-    
+
     /// Force set the window icon itself
     fn force_set_window_icon(window: &gtk::Window, textures: &Arc<Mutex<HashMap<String, gdk::Texture>>>) {
         let textures_guard = textures.lock().unwrap();
@@ -133,15 +245,16 @@ impl IconHijacker {
                 let icon_theme = gtk::IconTheme::for_display(&display);
                 let search_paths = icon_theme.search_path();
                 info!("🔍 Icon theme search paths: {:?}", search_paths);
-                
+
                 // Check if icon theme can find our icon
                 if icon_theme.has_icon(icon_name) {
                     info!("✅ Icon theme HAS icon: {}", icon_name);
                 } else {
                     warn!("❌ Icon theme MISSING icon: {}", icon_name);
-                    
-                    // Force add a search path with our icon
-                    Self::force_create_window_icon(icon_name, &icon_theme);
+
+                    // Install a real hicolor icon tree instead of one fixed-size
+                    // SVG, so GTK's own size-aware lookup resolves it correctly
+                    Self::install_hicolor_theme(&icon_theme);
                 }
                 
                 info!("🚨 FORCED window icon: {} for window: {:?}", icon_name, window.title());
@@ -150,200 +263,248 @@ impl IconHijacker {
         }
     }
     
-    /// Force create window icon in icon theme
-    fn force_create_window_icon(icon_name: &str, icon_theme: &gtk::IconTheme) {
-        use std::io::Write;
-        
-        if let Ok(temp_dir) = std::env::temp_dir().canonicalize() {
-            let icon_dir = temp_dir.join("amberol-window-icons");
-            if std::fs::create_dir_all(&icon_dir).is_ok() {
-                let icon_file = icon_dir.join(format!("{}.svg", icon_name));
-                
-                // Create a simple SVG icon
-                let svg_content = format!(r##"<?xml version="1.0" encoding="UTF-8"?>
-<svg width="48" height="48" viewBox="0 0 48 48" xmlns="http://www.w3.org/2000/svg">
-  <circle cx="12" cy="36" r="6" fill="#ff8c00" stroke="#333" stroke-width="1"/>
-  <circle cx="32" cy="28" r="5" fill="#ff8c00" stroke="#333" stroke-width="1"/>
-  <path d="M18 36 L18 12 L38 8 L38 28" stroke="#333" stroke-width="3" fill="none"/>
-</svg>"##);
-                
-                if std::fs::write(&icon_file, svg_content).is_ok() {
-                    icon_theme.add_search_path(&icon_dir);
-                    info!("🚨 FORCE CREATED window icon file: {:?}", icon_file);
-                }
-            }
-        }
-    }
-    
-    /// Recursively hijack all widgets in a tree
-    fn hijack_widget_tree(widget: &gtk::Widget, textures: &Arc<Mutex<HashMap<String, gdk::Texture>>>) {
-        // Check for images
-        if let Some(image) = widget.downcast_ref::<gtk::Image>() {
-            Self::hijack_image(image, textures);
-        }
-        
-        // Check for buttons with icons
-        if let Some(button) = widget.downcast_ref::<gtk::Button>() {
-            Self::hijack_button(button, textures);
-        }
-        
-        // Check for about windows specifically
-        if let Some(about_window) = widget.downcast_ref::<adw::AboutWindow>() {
-            Self::hijack_about_window(about_window, textures);
-        }
-        
-        // Recurse into children
-        let mut child = widget.first_child();
-        while let Some(current_child) = child {
-            Self::hijack_widget_tree(&current_child, textures);
-            child = current_child.next_sibling();
-        }
+    /// Pixel sizes `install_hicolor_theme` rasterizes the app icon at,
+    /// matching the `hicolor` theme's standard `apps` subdirectories.
+    const HICOLOR_SIZES: [i32; 8] = [16, 22, 24, 32, 48, 64, 128, 256];
+
+    /// `$XDG_DATA_HOME/icons/hicolor`, falling back to
+    /// `~/.local/share/icons/hicolor`.
+    fn hicolor_theme_dir() -> Option<std::path::PathBuf> {
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .map(std::path::PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| std::path::PathBuf::from(home).join(".local/share")))?;
+        Some(data_home.join("icons").join("hicolor"))
     }
-    
-    /// Hijack a specific image widget
-    fn hijack_image(image: &gtk::Image, textures: &Arc<Mutex<HashMap<String, gdk::Texture>>>) {
-        let textures_guard = textures.lock().unwrap();
-        
-        // Try to determine what icon this image is supposed to show
-        let icon_name = match image.storage_type() {
-            gtk::ImageType::IconName => image.icon_name().map(|s| s.to_string()),
-            gtk::ImageType::Gicon => {
-                if let Some(gicon) = image.gicon() {
-                    if let Some(themed_icon) = gicon.downcast_ref::<gtk::gio::ThemedIcon>() {
-                        themed_icon.names().get(0).map(|s| s.to_string())
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            }
-            _ => None,
+
+    /// Install a real `hicolor` icon tree for `io.bassi.Amberol`: a PNG at
+    /// each of [`Self::HICOLOR_SIZES`] plus a `scalable/apps` SVG, and an
+    /// `index.theme` listing them, so taskbars, the shell, and the about
+    /// dialog all resolve the right size through GTK's own lookup instead
+    /// of being force-painted a single fixed texture.
+    fn install_hicolor_theme(icon_theme: &gtk::IconTheme) {
+        let Some(theme_dir) = Self::hicolor_theme_dir() else {
+            warn!("⚠️ Could not determine a writable icons directory for the hicolor theme");
+            return;
         };
-        
-        if let Some(icon_name) = icon_name {
-            // Only hijack icons we know should be replaced
-            if Self::should_hijack_icon(&icon_name) {
-                if let Some(texture) = textures_guard.get(&icon_name) {
-                    image.set_paintable(Some(texture));
-                    info!("🚨 HIJACKED image icon: {}", icon_name);
-                } else {
-                    // Force set to app icon if we don't have a specific replacement
-                    if let Some(texture) = textures_guard.get("io.bassi.Amberol") {
-                        image.set_paintable(Some(texture));
-                        info!("🚨 FORCE REPLACED unknown icon '{}' with app icon", icon_name);
-                    }
+
+        for size in Self::HICOLOR_SIZES {
+            let apps_dir = theme_dir.join(format!("{size}x{size}")).join("apps");
+            if std::fs::create_dir_all(&apps_dir).is_err() {
+                continue;
+            }
+
+            let Some(mut surface) = Self::create_app_icon_surface(size) else {
+                continue;
+            };
+            let png_path = apps_dir.join("io.bassi.Amberol.png");
+            if let Ok(mut file) = std::fs::File::create(&png_path) {
+                if let Err(e) = surface.write_to_png(&mut file) {
+                    warn!("⚠️ Failed to write {png_path:?}: {e}");
                 }
             }
         }
+
+        let scalable_dir = theme_dir.join("scalable").join("apps");
+        if std::fs::create_dir_all(&scalable_dir).is_ok() {
+            let svg_path = scalable_dir.join("io.bassi.Amberol.svg");
+            let _ = std::fs::write(&svg_path, Self::app_icon_svg());
+        }
+
+        Self::write_index_theme(&theme_dir);
+
+        icon_theme.add_search_path(theme_dir.parent().unwrap_or(&theme_dir));
+        info!("🚨 Installed hicolor icon tree at {theme_dir:?}");
     }
-    
-    /// Check if an icon should be hijacked (be more selective)
-    fn should_hijack_icon(icon_name: &str) -> bool {
-        matches!(icon_name,
-            "io.bassi.Amberol" |
-            "io.bassi.Amberol.Devel" |
-            "web-browser-symbolic" |
-            "user-home-symbolic" |
-            "document-edit-symbolic" |
-            "bug-symbolic" |
-            "system-search-symbolic" |
-            "open-menu-symbolic" |
-            "image-missing"
-            // Deliberately NOT including "audio-only-symbolic" - let the existing system handle it
-        )
+
+    /// Render the app icon's musical-note glyph at `size` pixels, scaling
+    /// the same 64-unit design grid `create_app_icon_texture` draws at.
+    fn create_app_icon_surface(size: i32) -> Option<gtk::cairo::ImageSurface> {
+        use gtk::cairo;
+
+        let mut surface = cairo::ImageSurface::create(cairo::Format::ARgb32, size, size).ok()?;
+        let cr = cairo::Context::new(&surface).ok()?;
+
+        cr.set_source_rgba(0.0, 0.0, 0.0, 0.0);
+        cr.paint().unwrap_or(());
+        cr.scale(size as f64 / 64.0, size as f64 / 64.0);
+        Self::draw_app_icon(&cr);
+
+        drop(cr);
+        surface.flush();
+        Some(surface)
     }
-    
-    /// Hijack a specific button widget
-    fn hijack_button(button: &gtk::Button, textures: &Arc<Mutex<HashMap<String, gdk::Texture>>>) {
-        if let Some(icon_name) = button.icon_name() {
-            let icon_name_str = icon_name.to_string();
-            // Only hijack buttons with icons we want to replace
-            if Self::should_hijack_icon(&icon_name_str) {
-                let textures_guard = textures.lock().unwrap();
-                if let Some(texture) = textures_guard.get(&icon_name_str) {
-                    // Remove the button's icon and add our own image
-                    button.set_icon_name("");
-                    
-                    let image = gtk::Image::new();
-                    image.set_paintable(Some(texture));
-                    button.set_child(Some(&image));
-                    info!("🚨 HIJACKED button icon: {}", icon_name_str);
-                }
-            }
-        }
+
+    /// The app icon's glyph, in a 64-unit design grid, factored out of
+    /// [`Self::create_app_icon_texture`] so [`Self::create_app_icon_surface`]
+    /// can render it at arbitrary sizes for the hicolor tree.
+    fn draw_app_icon(cr: &gtk::cairo::Context) {
+        cr.set_source_rgba(1.0, 0.55, 0.0, 1.0);
+
+        cr.arc(16.0, 48.0, 8.0, 0.0, 2.0 * std::f64::consts::PI);
+        cr.fill().unwrap_or(());
+
+        cr.arc(44.0, 38.0, 6.0, 0.0, 2.0 * std::f64::consts::PI);
+        cr.fill().unwrap_or(());
+
+        cr.set_line_width(4.0);
+        cr.move_to(24.0, 48.0);
+        cr.line_to(24.0, 16.0);
+        cr.line_to(50.0, 12.0);
+        cr.line_to(50.0, 38.0);
+        cr.stroke().unwrap_or(());
     }
-    
-    /// Hijack about window specifically
-    fn hijack_about_window(about_window: &adw::AboutWindow, textures: &Arc<Mutex<HashMap<String, gdk::Texture>>>) {
-        info!("🚨 HIJACKING about window!");
-        
-        // Force set application icon
-        about_window.set_application_icon("io.bassi.Amberol");
-        
-        // Try to find and replace all icons in the about window
-        Self::hijack_widget_tree(about_window.upcast_ref::<gtk::Widget>(), textures);
+
+    /// The same musical-note glyph as [`Self::draw_app_icon`], as a
+    /// scale-independent SVG for `scalable/apps`.
+    fn app_icon_svg() -> String {
+        r##"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="64" height="64" viewBox="0 0 64 64">
+<g fill="#ff8c00" stroke="#ff8c00" fill-rule="evenodd">
+<circle cx="16" cy="48" r="8"/>
+<circle cx="44" cy="38" r="6"/>
+<path d="M24 48 L24 16 L50 12 L50 38" stroke-width="4" fill="none"/>
+</g>
+</svg>"##
+            .to_string()
+    }
+
+    /// Write (or overwrite) `index.theme` for the tree built by
+    /// [`Self::install_hicolor_theme`], listing each `apps` subdirectory at
+    /// its matching size, per the freedesktop Icon Theme Specification.
+    fn write_index_theme(theme_dir: &std::path::Path) {
+        let mut directories: Vec<String> =
+            Self::HICOLOR_SIZES.iter().map(|size| format!("{size}x{size}/apps")).collect();
+        directories.push("scalable/apps".to_string());
+
+        let mut contents = String::new();
+        contents.push_str("[Icon Theme]\n");
+        contents.push_str("Name=hicolor\n");
+        contents.push_str("Comment=Fallback icon theme\n");
+        contents.push_str(&format!("Directories={}\n", directories.join(",")));
+
+        for size in Self::HICOLOR_SIZES {
+            contents.push_str(&format!(
+                "\n[{size}x{size}/apps]\nSize={size}\nContext=Applications\nType=Fixed\n"
+            ));
+        }
+        contents.push_str("\n[scalable/apps]\nSize=64\nMinSize=16\nMaxSize=512\nContext=Applications\nType=Scalable\n");
+
+        let index_path = theme_dir.join("index.theme");
+        if let Err(e) = std::fs::write(&index_path, contents) {
+            warn!("⚠️ Failed to write {index_path:?}: {e}");
+        }
     }
     
-    /// Hook into window creation to catch new windows
-    fn hook_window_creation(&self) {
+    /// Hook into window creation to force the actual window icon name on
+    /// each new window. This no longer needs to walk the widget tree too:
+    /// stock icon names are already resolved by GTK itself once
+    /// [`Self::install_replacement_icon_theme`] has registered them.
+    fn hook_window_creation(self: &Rc<Self>) {
         // This is a bit tricky in GTK4, but we can monitor application windows
         if let Some(app) = gtk::gio::Application::default() {
             if let Some(gtk_app) = app.downcast_ref::<gtk::Application>() {
-                let textures = self.replacement_textures.clone();
-                
-                gtk_app.connect_window_added(move |_app, window| {
+                gtk_app.connect_window_added(glib::clone!(@weak self as this => move |_app, window| {
                     info!("🚨 NEW WINDOW DETECTED - hijacking icons");
-                    
+
+                    this.windows.borrow_mut().push(window.downgrade());
+
                     // Wait a bit for the window to be fully constructed
-                    let textures_clone = textures.clone();
+                    let textures_clone = this.replacement_textures.clone();
                     let window_weak = window.downgrade();
-                    
+
                     glib::timeout_add_local_once(std::time::Duration::from_millis(100), move || {
                         if let Some(window) = window_weak.upgrade() {
-                            Self::hijack_window_icons(&window, &textures_clone);
+                            Self::force_set_window_icon(&window, &textures_clone);
                         }
                     });
-                });
+                }));
             }
         }
     }
-    
-    /// Hook specifically into about dialog creation
-    fn hook_about_dialog_creation(&self) {
-        // We'll use a different approach - hook into the action
-        info!("🚨 Hooking about dialog creation");
+
+    /// Re-apply the current app-id texture to every window we've hooked so
+    /// far. Called after [`Self::set_song`] swaps in new cover art, so an
+    /// already-open window's icon updates immediately instead of only the
+    /// next window that's created.
+    fn refresh_window_icons(&self) {
+        let mut windows = self.windows.borrow_mut();
+        windows.retain(|weak| weak.upgrade().is_some());
+        for weak in windows.iter() {
+            if let Some(window) = weak.upgrade() {
+                Self::force_set_window_icon(&window, &self.replacement_textures);
+            }
+        }
     }
-    
+
+    /// Pixel size cover art is cropped/downscaled to for use as a window
+    /// or tray texture, matching [`Self::create_app_icon_texture`]'s size.
+    const COVER_ICON_SIZE: i32 = 64;
+
+    /// Downscale `source` (the song's embedded cover art) to a rounded
+    /// square via [`Self::create_texture_from_drawing`], the same helper
+    /// every drawn fallback glyph goes through, so a real photo and a
+    /// hand-drawn note end up as textures of the same shape and size.
+    fn create_cover_texture(source: &gdk::Texture) -> Option<gdk::Texture> {
+        use gtk::cairo;
+        use std::f64::consts::{FRAC_PI_2, PI};
+
+        let (src_w, src_h) = (source.width(), source.height());
+        let src_stride = src_w as usize * 4;
+        let mut src_data = vec![0u8; src_stride * src_h as usize];
+        // `GdkTexture::download` always hands back premultiplied ARGB32
+        // data, i.e. exactly what `cairo::ImageSurface::create_for_data`
+        // with `Format::ARgb32` expects.
+        source.download(&mut src_data, src_stride);
+        let src_surface = cairo::ImageSurface::create_for_data(
+            src_data,
+            cairo::Format::ARgb32,
+            src_w,
+            src_h,
+            src_stride as i32,
+        )
+        .ok()?;
+
+        Self::create_texture_from_drawing(Self::COVER_ICON_SIZE, move |cr| {
+            let size = Self::COVER_ICON_SIZE as f64;
+            let radius = size * 0.18;
+
+            cr.new_sub_path();
+            cr.arc(size - radius, radius, radius, -FRAC_PI_2, 0.0);
+            cr.arc(size - radius, size - radius, radius, 0.0, FRAC_PI_2);
+            cr.arc(radius, size - radius, radius, FRAC_PI_2, PI);
+            cr.arc(radius, radius, radius, PI, 3.0 * FRAC_PI_2);
+            cr.close_path();
+            cr.clip();
+
+            cr.scale(size / src_w as f64, size / src_h as f64);
+            if cr.set_source_surface(&src_surface, 0.0, 0.0).is_err() {
+                return false;
+            }
+            cr.paint().unwrap_or(());
+            true
+        })
+    }
+
+    /// The texture currently installed under `app_id`, if any — the same
+    /// artwork-or-fallback a window icon gets from
+    /// [`Self::force_set_window_icon`], for any other consumer (e.g. the
+    /// SNI tray's `IconPixmap`) that wants to mirror it.
+    pub fn current_icon_texture(&self, app_id: &str) -> Option<gdk::Texture> {
+        self.replacement_textures.lock().unwrap().get(app_id).cloned()
+    }
+
     // Icon creation methods
     fn create_app_icon_texture() -> Option<gdk::Texture> {
         Self::create_texture_from_drawing(64, |cr| {
-            // Musical note - orange/gold color
-            cr.set_source_rgba(1.0, 0.55, 0.0, 1.0);
-            
-            // Main note head
-            cr.arc(16.0, 48.0, 8.0, 0.0, 2.0 * std::f64::consts::PI);
-            cr.fill().unwrap_or(());
-            
-            // Second note head
-            cr.arc(44.0, 38.0, 6.0, 0.0, 2.0 * std::f64::consts::PI);
-            cr.fill().unwrap_or(());
-            
-            // Note stem
-            cr.set_line_width(4.0);
-            cr.move_to(24.0, 48.0);
-            cr.line_to(24.0, 16.0);
-            cr.line_to(50.0, 12.0);
-            cr.line_to(50.0, 38.0);
-            cr.stroke().unwrap_or(());
-            
+            Self::draw_app_icon(cr);
             true
         })
     }
-    
-    fn create_web_icon_texture() -> Option<gdk::Texture> {
+
+    fn create_web_icon_texture(palette: &IconPalette) -> Option<gdk::Texture> {
+        let (r, g, b, a) = palette.foreground;
         Self::create_texture_from_drawing(32, |cr| {
-            cr.set_source_rgba(0.2, 0.6, 0.9, 1.0);
+            cr.set_source_rgba(r, g, b, a);
             cr.set_line_width(2.0);
             
             // Globe outline
@@ -364,9 +525,10 @@ impl IconHijacker {
         })
     }
     
-    fn create_bug_icon_texture() -> Option<gdk::Texture> {
+    fn create_bug_icon_texture(palette: &IconPalette) -> Option<gdk::Texture> {
+        let (r, g, b, a) = palette.error;
         Self::create_texture_from_drawing(32, |cr| {
-            cr.set_source_rgba(0.8, 0.2, 0.2, 1.0);
+            cr.set_source_rgba(r, g, b, a);
             cr.set_line_width(2.0);
             
             // Bug body
@@ -387,9 +549,10 @@ impl IconHijacker {
         })
     }
     
-    fn create_search_icon_texture() -> Option<gdk::Texture> {
+    fn create_search_icon_texture(palette: &IconPalette) -> Option<gdk::Texture> {
+        let (r, g, b, a) = palette.foreground;
         Self::create_texture_from_drawing(32, |cr| {
-            cr.set_source_rgba(0.3, 0.3, 0.3, 1.0);
+            cr.set_source_rgba(r, g, b, a);
             cr.set_line_width(3.0);
             
             // Magnifying glass
@@ -405,9 +568,10 @@ impl IconHijacker {
         })
     }
     
-    fn create_menu_icon_texture() -> Option<gdk::Texture> {
+    fn create_menu_icon_texture(palette: &IconPalette) -> Option<gdk::Texture> {
+        let (r, g, b, a) = palette.foreground;
         Self::create_texture_from_drawing(32, |cr| {
-            cr.set_source_rgba(0.3, 0.3, 0.3, 1.0);
+            cr.set_source_rgba(r, g, b, a);
             cr.set_line_width(3.0);
             
             // Hamburger menu
@@ -426,11 +590,13 @@ impl IconHijacker {
         Self::create_app_icon_texture() // Same as app icon
     }
     
-    fn create_folder_icon_texture() -> Option<gdk::Texture> {
-        Self::create_texture_from_drawing(32, |cr| {
-            cr.set_source_rgba(0.9, 0.7, 0.3, 1.0);
+    fn create_folder_icon_texture(palette: &IconPalette) -> Option<gdk::Texture> {
+        let (r, g, b, a) = palette.warning;
+        let (fr, fg, fb, fa) = palette.foreground;
+        Self::create_texture_from_drawing(32, move |cr| {
+            cr.set_source_rgba(r, g, b, a);
             cr.set_line_width(2.0);
-            
+
             // Folder outline
             cr.move_to(4.0, 10.0);
             cr.line_to(4.0, 26.0);
@@ -440,12 +606,12 @@ impl IconHijacker {
             cr.line_to(16.0, 10.0);
             cr.close_path();
             cr.stroke().unwrap_or(());
-            
+
             // Small music note inside
-            cr.set_source_rgba(0.6, 0.4, 0.2, 1.0);
+            cr.set_source_rgba(fr, fg, fb, fa);
             cr.arc(14.0, 20.0, 2.0, 0.0, 2.0 * std::f64::consts::PI);
             cr.fill().unwrap_or(());
-            
+
             true
         })
     }
@@ -483,7 +649,42 @@ impl IconHijacker {
                 }
             }
         }
-        
+
         None
     }
+}
+
+impl Controller for IconHijacker {
+    fn set_playback_state(&self, _state: &PlaybackState) {}
+
+    /// Use the now-playing song's embedded cover art as the window/tray
+    /// app-id texture, falling back to the drawn musical note for songs
+    /// with no art, then push it out to every already-open window.
+    fn set_song(&self, song: &Song) {
+        let album = song.album();
+
+        let cover = self.cover_cache.borrow().get(&album).cloned().or_else(|| {
+            let source = song.cover_texture()?;
+            let texture = Self::create_cover_texture(&source)?;
+            self.cover_cache.borrow_mut().insert(album.clone(), texture.clone());
+            Some(texture)
+        });
+
+        let mut textures = self.replacement_textures.lock().unwrap();
+        for app_id in ["io.bassi.Amberol", "io.bassi.Amberol.Devel"] {
+            let texture = cover.clone().or_else(Self::create_app_icon_texture);
+            if let Some(texture) = texture {
+                textures.insert(app_id.to_string(), texture);
+            }
+        }
+        drop(textures);
+
+        info!("🎨 Updated app-id textures for now playing: {album:?}");
+        self.install_replacement_icon_theme();
+        self.refresh_window_icons();
+    }
+
+    fn set_position(&self, _position: u64) {}
+
+    fn set_repeat_mode(&self, _repeat: RepeatMode) {}
 }
\ No newline at end of file
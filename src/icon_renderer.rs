@@ -4,11 +4,193 @@
 //! Programmatic icon rendering for reliable cross-platform display
 //! This module creates icons using Cairo drawing instead of SVG files
 
-use gtk::{cairo, gdk, prelude::*};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+
+use gtk::{cairo, gdk, gio, glib, prelude::*};
 use log::{info, warn};
 
 const ICON_SIZE: i32 = 16;
 const ICON_COLOR: (f64, f64, f64) = (0.18, 0.20, 0.21); // #2e3436 in RGB
+const ICON_COLOR_DARK: (f64, f64, f64) = (0.88, 0.88, 0.89); // #e0e0e3 in RGB, for dark style variants
+/// Fallback accent color (libadwaita's default accent blue, `#3584e4`),
+/// used wherever [`ColorContext::resolve`] has no widget to read the
+/// user's actual accent preference from.
+const ACCENT_COLOR_FALLBACK: (f64, f64, f64) = (0.208, 0.518, 0.894);
+
+thread_local! {
+    /// Pre-rendered glyphs keyed by `(icon_name, width, height, scale, dark)`,
+    /// so a theme or color-scheme switch only has to redraw the handful of
+    /// on-screen buttons once instead of every icon re-walking its Cairo
+    /// path on every frame.
+    static ICON_CACHE: RefCell<HashMap<(String, i32, i32, i32, bool), cairo::ImageSurface>> =
+        RefCell::new(HashMap::new());
+    /// Weak refs to every `DrawingArea` handed out by [`IconRenderer::create_icon_widget`],
+    /// so [`IconRenderer::refresh_all`] can invalidate and redraw them in place.
+    static TRACKED_AREAS: RefCell<Vec<glib::WeakRef<gtk::DrawingArea>>> = RefCell::new(Vec::new());
+    /// `(image, icon_name, size)` for every themed fallback handed out by
+    /// [`IconRenderer::resolve_icon`], so [`IconRenderer::refresh_all`] can
+    /// re-resolve them too: a theme switch can change whether `icon_name`
+    /// exists at all, not just how it's colored.
+    static TRACKED_IMAGES: RefCell<Vec<(glib::WeakRef<gtk::Image>, String, i32)>> =
+        RefCell::new(Vec::new());
+    static THEME_WATCH_INSTALLED: Cell<bool> = Cell::new(false);
+    /// Textures handed out by [`IconRenderer::render`], keyed by
+    /// `(name, size, scale, dark)` so a light/dark switch gets its own
+    /// entry instead of serving back the wrong variant.
+    static TEXTURE_CACHE: RefCell<HashMap<(IconName, i32, i32, bool), gdk::Texture>> =
+        RefCell::new(HashMap::new());
+}
+
+/// The colors the `draw_*` helpers paint with, resolved from a widget's
+/// live style context when one is available (mirrors
+/// `crate::icon_hijacker::IconPalette`), so glyphs recolor for the
+/// current light/dark preference and accent the same way real symbolic
+/// icons and `-gtk-icon-palette` do, instead of always using the fixed
+/// [`ICON_COLOR`]/[`ICON_COLOR_DARK`] pair.
+struct ColorContext {
+    foreground: (f64, f64, f64),
+    accent: (f64, f64, f64),
+}
+
+impl ColorContext {
+    /// Resolve from `widget`'s style context when given one, falling back
+    /// to `dark`'s [`ICON_COLOR`]/[`ICON_COLOR_DARK`] and
+    /// [`ACCENT_COLOR_FALLBACK`] for contexts with no widget tree to read
+    /// from yet -- the tray/ICO/HICON surfaces rendered off the main
+    /// window.
+    fn resolve(widget: Option<&gtk::Widget>, dark: bool) -> Self {
+        let foreground_fallback = if dark { ICON_COLOR_DARK } else { ICON_COLOR };
+
+        let Some(widget) = widget else {
+            return Self { foreground: foreground_fallback, accent: ACCENT_COLOR_FALLBACK };
+        };
+
+        let style_context = widget.style_context();
+        let lookup = |name: &str, fallback: (f64, f64, f64)| -> (f64, f64, f64) {
+            style_context
+                .lookup_color(name)
+                .map(|c| (c.red() as f64, c.green() as f64, c.blue() as f64))
+                .unwrap_or(fallback)
+        };
+
+        Self {
+            foreground: lookup("theme_fg_color", foreground_fallback),
+            accent: lookup("accent_color", ACCENT_COLOR_FALLBACK),
+        }
+    }
+}
+
+/// Every glyph [`IconRenderer::draw_icon_path`] can draw, as a typed
+/// registry instead of callers having to know the bare icon-name strings
+/// (and risk a typo `draw_icon_path` would silently treat as "unknown").
+/// [`Self::as_str`] is the single place mapping a variant back to the
+/// name GTK/GSettings/the rest of Amberol actually reference it by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IconName {
+    PlaybackStart,
+    PlaybackPause,
+    SkipBackward,
+    SkipForward,
+    PlaylistConsecutive,
+    PlaylistRepeat,
+    PlaylistRepeatSong,
+    PlaylistShuffle,
+    ViewQueue,
+    ViewQueueRtl,
+    AppRemove,
+    AudioOnly,
+    GoPrevious,
+    FolderMusic,
+    SeekBackward,
+    SeekForward,
+    PlaybackStop,
+    InputMicrophone,
+    InputLine,
+    MediaOptical,
+    EditSelectAll,
+    EditClearAll,
+    SelectionMode,
+    VolumeMuted,
+    VolumeOff,
+    VolumeLow,
+    VolumeMedium,
+    VolumeHigh,
+    AmberolApp,
+}
+
+impl IconName {
+    /// The icon-name string this variant resolves to everywhere else in
+    /// Amberol (buttons, `resolve_icon`, GSettings bindings).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PlaybackStart => "media-playback-start-symbolic",
+            Self::PlaybackPause => "media-playback-pause-symbolic",
+            Self::SkipBackward => "media-skip-backward-symbolic",
+            Self::SkipForward => "media-skip-forward-symbolic",
+            Self::PlaylistConsecutive => "media-playlist-consecutive-symbolic",
+            Self::PlaylistRepeat => "media-playlist-repeat-symbolic",
+            Self::PlaylistRepeatSong => "media-playlist-repeat-song-symbolic",
+            Self::PlaylistShuffle => "media-playlist-shuffle-symbolic",
+            Self::ViewQueue => "view-queue-symbolic",
+            Self::ViewQueueRtl => "view-queue-rtl-symbolic",
+            Self::AppRemove => "app-remove-symbolic",
+            Self::AudioOnly => "audio-only-symbolic",
+            Self::GoPrevious => "go-previous-symbolic",
+            Self::FolderMusic => "folder-music-symbolic",
+            Self::SeekBackward => "media-seek-backward-symbolic",
+            Self::SeekForward => "media-seek-forward-symbolic",
+            Self::PlaybackStop => "media-playback-stop-symbolic",
+            Self::InputMicrophone => "audio-input-microphone-symbolic",
+            Self::InputLine => "audio-input-line-symbolic",
+            Self::MediaOptical => "media-optical-symbolic",
+            Self::EditSelectAll => "edit-select-all-symbolic",
+            Self::EditClearAll => "edit-clear-all-symbolic",
+            Self::SelectionMode => "selection-mode-symbolic",
+            Self::VolumeMuted => "audio-volume-muted-symbolic",
+            Self::VolumeOff => "audio-volume-off-symbolic",
+            Self::VolumeLow => "audio-volume-low-symbolic",
+            Self::VolumeMedium => "audio-volume-medium-symbolic",
+            Self::VolumeHigh => "audio-volume-high-symbolic",
+            Self::AmberolApp => "io.bassi.Amberol",
+        }
+    }
+
+    /// Every variant, in the order GTK's size-matching directories are
+    /// usually generated -- used by [`IconRenderer::register_named_icons`]
+    /// to warm the texture cache for the whole set at once.
+    const ALL: &'static [Self] = &[
+        Self::PlaybackStart,
+        Self::PlaybackPause,
+        Self::SkipBackward,
+        Self::SkipForward,
+        Self::PlaylistConsecutive,
+        Self::PlaylistRepeat,
+        Self::PlaylistRepeatSong,
+        Self::PlaylistShuffle,
+        Self::ViewQueue,
+        Self::ViewQueueRtl,
+        Self::AppRemove,
+        Self::AudioOnly,
+        Self::GoPrevious,
+        Self::FolderMusic,
+        Self::SeekBackward,
+        Self::SeekForward,
+        Self::PlaybackStop,
+        Self::InputMicrophone,
+        Self::InputLine,
+        Self::MediaOptical,
+        Self::EditSelectAll,
+        Self::EditClearAll,
+        Self::SelectionMode,
+        Self::VolumeMuted,
+        Self::VolumeOff,
+        Self::VolumeLow,
+        Self::VolumeMedium,
+        Self::VolumeHigh,
+        Self::AmberolApp,
+    ];
+}
 
 pub struct IconRenderer;
 
@@ -33,11 +215,21 @@ impl IconRenderer {
             "audio-only-symbolic" |
             "go-previous-symbolic" |
             "folder-music-symbolic" |
+            // Transport controls
+            "media-seek-backward-symbolic" |
+            "media-seek-forward-symbolic" |
+            "media-playback-stop-symbolic" |
+            // Audio source/device glyphs
+            "audio-input-microphone-symbolic" |
+            "audio-input-line-symbolic" |
+            "media-optical-symbolic" |
+            // Selection controls
             "edit-select-all-symbolic" |
             "edit-clear-all-symbolic" |
             "selection-mode-symbolic" |
             // App icon
             "audio-volume-muted-symbolic" |
+            "audio-volume-off-symbolic" |
             "audio-volume-low-symbolic" |
             "audio-volume-medium-symbolic" |
             "audio-volume-high-symbolic" |
@@ -102,6 +294,42 @@ impl IconRenderer {
         Self::set_status_page_icon_programmatic(status_page, icon_name);
         true // Always use programmatic rendering now
     }
+
+    /// Set a volume button's icon to the glyph matching `volume` (0-100)
+    /// and `muted`, mirroring the bucketing desktop mixers use. Always
+    /// goes through the same programmatic-draw path as every other icon
+    /// here, since `PlaybackControl::set_repeat_mode`'s SVG-to-`Texture`
+    /// probe already showed theme icons can silently fail to render on
+    /// some platforms.
+    pub fn set_volume_icon_programmatic(button: &gtk::Button, volume: f64, muted: bool) {
+        Self::set_button_icon_programmatic(button, Self::volume_icon_name(volume, muted));
+    }
+
+    /// The icon name for `volume` (0-100) and `muted`: `muted` wins
+    /// outright, then `off` for a silent-but-unmuted level (falling back
+    /// to `muted` if this icon set doesn't define one), then low/medium/high
+    /// split at the thirds a volume slider naturally falls into.
+    fn volume_icon_name(volume: f64, muted: bool) -> &'static str {
+        if muted {
+            return "audio-volume-muted-symbolic";
+        }
+
+        if volume <= 0.0 {
+            return if Self::supports_icon("audio-volume-off-symbolic") {
+                "audio-volume-off-symbolic"
+            } else {
+                "audio-volume-muted-symbolic"
+            };
+        }
+
+        if volume <= 33.0 {
+            "audio-volume-low-symbolic"
+        } else if volume <= 66.0 {
+            "audio-volume-medium-symbolic"
+        } else {
+            "audio-volume-high-symbolic"
+        }
+    }
     
     /// Get fallback text for icons when programmatic rendering fails
     fn get_icon_fallback_text(icon_name: &str) -> String {
@@ -124,6 +352,7 @@ impl IconRenderer {
             "edit-clear-all-symbolic" => "⚹".to_string(),
             "selection-mode-symbolic" => "☑".to_string(),
             "audio-volume-muted-symbolic" => "🔇".to_string(),
+            "audio-volume-off-symbolic" => "🔈".to_string(),
             "audio-volume-low-symbolic" => "🔈".to_string(),
             "audio-volume-medium-symbolic" => "🔉".to_string(),
             "audio-volume-high-symbolic" => "🔊".to_string(),
@@ -201,69 +430,324 @@ impl IconRenderer {
     /// Create a programmatically drawn icon as a drawable widget
     pub fn create_icon_widget(icon_name: &str) -> Option<gtk::DrawingArea> {
         info!("🎨 Creating programmatic icon widget: {}", icon_name);
-        
+
         let drawing_area = gtk::DrawingArea::new();
         drawing_area.set_content_width(ICON_SIZE);
         drawing_area.set_content_height(ICON_SIZE);
-        
+
         let icon_name_for_closure = icon_name.to_string();
         let icon_name_for_log = icon_name.to_string();
-        
-        drawing_area.set_draw_func(move |_area, cr, width, height| {
-            // Scale to fit the allocated size
-            let scale_x = width as f64 / ICON_SIZE as f64;
-            let scale_y = height as f64 / ICON_SIZE as f64;
-            let scale = scale_x.min(scale_y);
-            
-            cr.scale(scale, scale);
-            
-            // Clear background (transparent)
-            cr.set_source_rgba(0.0, 0.0, 0.0, 0.0);
-            cr.paint().unwrap_or_default();
-            
-            // Set drawing color
-            cr.set_source_rgb(ICON_COLOR.0, ICON_COLOR.1, ICON_COLOR.2);
-            cr.set_line_width(1.0);
-            
-            // Draw the specific icon
-            let _success = match icon_name_for_closure.as_str() {
-                // Media playback controls
-                "media-playback-start-symbolic" => Self::draw_play(cr),
-                "media-playback-pause-symbolic" => Self::draw_pause(cr),
-                "media-skip-backward-symbolic" => Self::draw_skip_backward(cr),
-                "media-skip-forward-symbolic" => Self::draw_skip_forward(cr),
-                // Playlist mode controls
-                "media-playlist-consecutive-symbolic" => Self::draw_consecutive(cr),
-                "media-playlist-repeat-symbolic" => Self::draw_repeat_all(cr),
-                "media-playlist-repeat-song-symbolic" => Self::draw_repeat_one(cr),
-                "media-playlist-shuffle-symbolic" => Self::draw_shuffle(cr),
-                // UI controls
-                "view-queue-symbolic" => Self::draw_queue(cr),
-                "view-queue-rtl-symbolic" => Self::draw_queue_rtl(cr),
-                "app-remove-symbolic" => Self::draw_remove(cr),
-                "audio-only-symbolic" => Self::draw_audio_only(cr),
-                "go-previous-symbolic" => Self::draw_go_previous(cr),
-                "folder-music-symbolic" => Self::draw_folder_music(cr),
-                "edit-select-all-symbolic" => Self::draw_select_all(cr),
-                "edit-clear-all-symbolic" => Self::draw_clear_all(cr),
-                "selection-mode-symbolic" => Self::draw_selection_mode(cr),
-                // Volume controls
-                "audio-volume-muted-symbolic" => Self::draw_volume_muted(cr),
-                "audio-volume-low-symbolic" => Self::draw_volume_low(cr),
-                "audio-volume-medium-symbolic" => Self::draw_volume_medium(cr),
-                "audio-volume-high-symbolic" => Self::draw_volume_high(cr),
-                // App icons
-                "io.bassi.Amberol" | "amberol" => Self::draw_amberol_app_icon(cr),
-                _ => {
-                    warn!("Unknown programmatic icon: {}", icon_name_for_closure);
-                    false
-                }
-            };
+
+        drawing_area.set_draw_func(move |area, cr, width, height| {
+            let dark = adw::StyleManager::default().is_dark();
+            let key = (
+                icon_name_for_closure.clone(),
+                width,
+                height,
+                area.scale_factor(),
+                dark,
+            );
+
+            let surface = ICON_CACHE
+                .with(|cache| cache.borrow().get(&key).cloned())
+                .or_else(|| {
+                    let widget = area.clone().upcast::<gtk::Widget>();
+                    let rendered = Self::render_icon_surface(
+                        &icon_name_for_closure,
+                        width,
+                        height,
+                        Some(&widget),
+                        dark,
+                    );
+                    if let Some(surface) = &rendered {
+                        ICON_CACHE.with(|cache| {
+                            cache.borrow_mut().insert(key.clone(), surface.clone())
+                        });
+                    }
+                    rendered
+                });
+
+            if let Some(surface) = surface {
+                let _ = cr.set_source_surface(&surface, 0.0, 0.0);
+                cr.paint().unwrap_or_default();
+            }
         });
-        
+
+        Self::track_icon_widget(&drawing_area);
+
         info!("✅ Successfully created programmatic icon widget: {}", icon_name_for_log);
         Some(drawing_area)
     }
+
+    /// Render `icon_name` into a fresh transparent ARGB32 surface sized to
+    /// fit `width`x`height` on the `ICON_SIZE` logical grid, using the
+    /// foreground/accent colors resolved by [`ColorContext::resolve`] from
+    /// `widget` (or `dark`'s fallback pair, if there's no widget yet).
+    fn render_icon_surface(
+        icon_name: &str,
+        width: i32,
+        height: i32,
+        widget: Option<&gtk::Widget>,
+        dark: bool,
+    ) -> Option<cairo::ImageSurface> {
+        let surface =
+            cairo::ImageSurface::create(cairo::Format::ARgb32, width.max(1), height.max(1)).ok()?;
+        let cr = cairo::Context::new(&surface).ok()?;
+
+        let scale_x = width as f64 / ICON_SIZE as f64;
+        let scale_y = height as f64 / ICON_SIZE as f64;
+        cr.scale(scale_x.min(scale_y), scale_x.min(scale_y));
+
+        cr.set_source_rgba(0.0, 0.0, 0.0, 0.0);
+        cr.paint().ok()?;
+
+        let ctx = ColorContext::resolve(widget, dark);
+        cr.set_source_rgb(ctx.foreground.0, ctx.foreground.1, ctx.foreground.2);
+        cr.set_line_width(1.0);
+
+        Self::draw_icon_path(icon_name, &cr, Some(ctx.accent));
+
+        Some(surface)
+    }
+
+    /// Render `icon_name` at `size` logical pixels and `scale` device scale
+    /// (e.g. 2 on a 200% monitor) as a ready-to-paint `gdk::Texture`, so
+    /// callers that need an exact pixel size -- an app icon set, a drag
+    /// icon, a notification image -- get a crisp vector redraw instead of
+    /// a scaled-up `ICON_SIZE`-logical bitmap. [`Self::render_icon_surface`]
+    /// already scales the Cairo context to fit the surface it's given, so
+    /// this just asks for one sized to `size * scale` pixels.
+    pub fn render_icon(icon_name: &str, size: i32, scale: i32) -> Option<gdk::Texture> {
+        let dark = adw::StyleManager::default().is_dark();
+        let pixels = (size * scale.max(1)).max(1);
+        let mut surface = Self::render_icon_surface(icon_name, pixels, pixels, None, dark)?;
+        Self::surface_to_texture(&mut surface)
+    }
+
+    /// Like [`Self::render_icon`], but keyed off the typed [`IconName`]
+    /// registry and memoized by `(name, size, scale, dark)`: repeat calls
+    /// for the same size/scale/style skip straight back to the cached `gdk::Texture`
+    /// instead of re-rastering through Cairo every time.
+    pub fn render(name: IconName, size: i32, scale: i32) -> Option<gdk::Texture> {
+        let dark = adw::StyleManager::default().is_dark();
+        let key = (name, size, scale, dark);
+
+        if let Some(texture) = TEXTURE_CACHE.with(|cache| cache.borrow().get(&key).cloned()) {
+            return Some(texture);
+        }
+
+        let texture = Self::render_icon(name.as_str(), size, scale)?;
+        TEXTURE_CACHE.with(|cache| cache.borrow_mut().insert(key, texture.clone()));
+        Some(texture)
+    }
+
+    /// Warm [`Self::render`]'s cache for every [`IconName`] at the
+    /// standard button size, then run
+    /// [`crate::icon_theme_provider::IconThemeProvider::setup_global_override`],
+    /// which writes its own SVG icon set under the icon-theme search path.
+    /// The warmed textures themselves stay in memory and are not written
+    /// to disk.
+    pub fn register_named_icons() {
+        for name in IconName::ALL {
+            Self::render(*name, ICON_SIZE, 1);
+        }
+
+        crate::icon_theme_provider::IconThemeProvider::setup_global_override();
+    }
+
+    /// Convert a rendered Cairo surface into a `gdk::Texture` via an
+    /// intermediate `Pixbuf`, the same conversion used by
+    /// `icon_theme_provider.rs` and `icon_replacer.rs`.
+    fn surface_to_texture(surface: &mut cairo::ImageSurface) -> Option<gdk::Texture> {
+        let width = surface.width();
+        let height = surface.height();
+        let stride = surface.stride();
+        let data = surface.data().ok()?;
+
+        let pixbuf = gtk::gdk_pixbuf::Pixbuf::from_bytes(
+            &glib::Bytes::from(&data[..]),
+            gtk::gdk_pixbuf::Colorspace::Rgb,
+            true,
+            8,
+            width,
+            height,
+            stride,
+        );
+
+        Some(gdk::Texture::for_pixbuf(&pixbuf))
+    }
+
+    /// Dispatch to the Cairo path for `icon_name`, assuming the caller
+    /// already set the source color and line width. Shared by the
+    /// cache-miss path in [`Self::render_icon_surface`] so there's a single
+    /// place mapping icon names to draw functions. `accent` is only
+    /// consumed by [`Self::draw_amberol_app_icon`]; every symbolic glyph
+    /// draws with whatever source color the caller already set.
+    fn draw_icon_path(icon_name: &str, cr: &cairo::Context, accent: Option<(f64, f64, f64)>) -> bool {
+        match icon_name {
+            // Media playback controls
+            "media-playback-start-symbolic" => Self::draw_play(cr),
+            "media-playback-pause-symbolic" => Self::draw_pause(cr),
+            "media-skip-backward-symbolic" => Self::draw_skip_backward(cr),
+            "media-skip-forward-symbolic" => Self::draw_skip_forward(cr),
+            "media-seek-backward-symbolic" => Self::draw_rewind(cr),
+            "media-seek-forward-symbolic" => Self::draw_fast_forward(cr),
+            "media-playback-stop-symbolic" => Self::draw_stop(cr),
+            "audio-input-microphone-symbolic" => Self::draw_mic(cr),
+            "audio-input-line-symbolic" => Self::draw_line_in(cr),
+            "media-optical-symbolic" => Self::draw_disc(cr),
+            // Playlist mode controls
+            "media-playlist-consecutive-symbolic" => Self::draw_consecutive(cr),
+            "media-playlist-repeat-symbolic" => Self::draw_repeat_all(cr),
+            "media-playlist-repeat-song-symbolic" => Self::draw_repeat_one(cr),
+            "media-playlist-shuffle-symbolic" => Self::draw_shuffle(cr),
+            // UI controls
+            "view-queue-symbolic" => Self::draw_queue(cr),
+            "view-queue-rtl-symbolic" => Self::draw_queue_rtl(cr),
+            "app-remove-symbolic" => Self::draw_remove(cr),
+            "audio-only-symbolic" => Self::draw_audio_only(cr),
+            "go-previous-symbolic" => Self::draw_go_previous(cr),
+            "folder-music-symbolic" => Self::draw_folder_music(cr),
+            "edit-select-all-symbolic" => Self::draw_select_all(cr),
+            "edit-clear-all-symbolic" => Self::draw_clear_all(cr),
+            "selection-mode-symbolic" => Self::draw_selection_mode(cr),
+            // Volume controls
+            "audio-volume-muted-symbolic" => Self::draw_volume_muted(cr),
+            "audio-volume-off-symbolic" => Self::draw_volume_off(cr),
+            "audio-volume-low-symbolic" => Self::draw_volume_low(cr),
+            "audio-volume-medium-symbolic" => Self::draw_volume_medium(cr),
+            "audio-volume-high-symbolic" => Self::draw_volume_high(cr),
+            // App icons
+            "io.bassi.Amberol" | "amberol" => Self::draw_amberol_app_icon(cr, accent),
+            _ => {
+                warn!("Unknown programmatic icon: {}", icon_name);
+                false
+            }
+        }
+    }
+
+    /// Register `area` with [`Self::refresh_all`] and make sure the
+    /// icon-theme/color-scheme watchers are installed (idempotent; the
+    /// watchers are process-global, not per-widget).
+    fn track_icon_widget(area: &gtk::DrawingArea) {
+        TRACKED_AREAS.with(|areas| areas.borrow_mut().push(area.downgrade()));
+        Self::ensure_theme_watch();
+    }
+
+    /// Resolve `icon_name` the way most GTK apps do: prefer the themed
+    /// symbolic icon, so it follows the user's icon theme and recolors for
+    /// free, and only fall back to [`Self::create_icon_widget`]'s
+    /// programmatic rendering when the theme doesn't have it -- or when
+    /// the `force-programmatic-icons` GSettings key forces the fallback,
+    /// for themes/platforms where the themed SVG renders broken (see the
+    /// Windows `image-missing` debugging in
+    /// `playback_control.rs::set_repeat_mode`).
+    pub fn resolve_icon(icon_name: &str, size: i32) -> Option<gtk::Widget> {
+        if !Self::force_programmatic() {
+            if let Some(display) = gdk::Display::default() {
+                let icon_theme = gtk::IconTheme::for_display(&display);
+                if icon_theme.has_icon(icon_name) {
+                    let image = gtk::Image::from_icon_name(icon_name);
+                    image.set_pixel_size(size);
+                    Self::track_icon_image(&image, icon_name, size);
+                    return Some(image.upcast());
+                }
+            }
+        }
+
+        Self::create_icon_widget(icon_name).map(|area| area.upcast())
+    }
+
+    /// Whether the `force-programmatic-icons` GSettings key is set,
+    /// forcing [`Self::resolve_icon`] to always use the Cairo-drawn
+    /// fallback even when the icon theme has the requested name.
+    /// Defaults to `false` (prefer the themed icon) if there's no running
+    /// `Application` to read the setting from.
+    fn force_programmatic() -> bool {
+        gio::Application::default()
+            .and_then(|app| {
+                app.downcast_ref::<crate::application::Application>()
+                    .map(|app| app.settings().boolean("force-programmatic-icons"))
+            })
+            .unwrap_or(false)
+    }
+
+    /// Register `image` with [`Self::refresh_all`], so a later theme
+    /// switch re-resolves it instead of just redrawing it: unlike the
+    /// programmatic glyphs, a themed `gtk::Image` needs `has_icon` and
+    /// `set_icon_name` run again, since the new theme may drop the icon
+    /// entirely rather than just changing how it looks. If the new theme
+    /// has no `icon_name`, or programmatic icons are forced, the image is
+    /// left as it was.
+    fn track_icon_image(image: &gtk::Image, icon_name: &str, size: i32) {
+        TRACKED_IMAGES.with(|images| {
+            images
+                .borrow_mut()
+                .push((image.downgrade(), icon_name.to_string(), size))
+        });
+        Self::ensure_theme_watch();
+    }
+
+    /// Connect to `gtk::IconTheme::for_display`'s "changed" signal,
+    /// `AdwStyleManager`'s dark-mode notify, and `GtkSettings`'
+    /// `gtk-theme-name` notify (the accent-color change proxy used by
+    /// `crate::icon_hijacker::IconHijacker::watch_theme_changes`) once per
+    /// process, so every live icon-theme, light/dark, or accent change
+    /// clears the glyph cache and redraws the icons this module created.
+    fn ensure_theme_watch() {
+        if THEME_WATCH_INSTALLED.with(|installed| installed.replace(true)) {
+            return;
+        }
+
+        if let Some(display) = gdk::Display::default() {
+            gtk::IconTheme::for_display(&display).connect_changed(|_| Self::refresh_all());
+
+            gtk::Settings::for_display(&display)
+                .connect_notify_local(Some("gtk-theme-name"), |_, _| Self::refresh_all());
+        }
+
+        adw::StyleManager::default().connect_dark_notify(|_| Self::refresh_all());
+    }
+
+    /// Clear the glyph cache and redraw every icon this module has handed
+    /// out, so button glyphs (repeat, shuffle, volume) recolor correctly
+    /// after a live GTK icon-theme switch or a light/dark `AdwStyleManager`
+    /// change instead of waiting for the next unrelated redraw.
+    pub fn refresh_all() {
+        ICON_CACHE.with(|cache| cache.borrow_mut().clear());
+        TEXTURE_CACHE.with(|cache| cache.borrow_mut().clear());
+
+        TRACKED_AREAS.with(|areas| {
+            areas.borrow_mut().retain(|weak| {
+                if let Some(area) = weak.upgrade() {
+                    area.queue_draw();
+                    true
+                } else {
+                    false
+                }
+            });
+        });
+
+        let force_programmatic = Self::force_programmatic();
+        TRACKED_IMAGES.with(|images| {
+            images.borrow_mut().retain(|(weak, icon_name, size)| {
+                let Some(image) = weak.upgrade() else {
+                    return false;
+                };
+
+                if !force_programmatic {
+                    if let Some(display) = gdk::Display::default() {
+                        if gtk::IconTheme::for_display(&display).has_icon(icon_name) {
+                            image.set_icon_name(Some(icon_name));
+                            image.set_pixel_size(*size);
+                        }
+                    }
+                }
+
+                true
+            });
+        });
+    }
     
     /// Create a high-resolution app icon for taskbar/tray usage
     pub fn create_app_icon_surface(size: i32) -> Option<cairo::ImageSurface> {
@@ -290,34 +774,337 @@ impl IconRenderer {
         cr.set_source_rgba(0.0, 0.0, 0.0, 0.0);
         cr.paint().ok()?;
         
-        // Draw the Amberol app icon
-        Self::draw_amberol_app_icon(&cr);
+        // Draw the Amberol app icon; this is the fixed-identity surface
+        // used for the taskbar/dock/ICO exporters, so it always uses the
+        // brand red rather than the live accent.
+        Self::draw_amberol_app_icon(&cr, None);
         
         info!("✅ Successfully created {}x{} app icon surface", size, size);
         Some(surface)
     }
-    
+
+    /// Composite the Amberol glyph with a bottom progress bar filled
+    /// proportionally to `progress` (0.0-1.0) and a small play/pause state
+    /// dot, for callers (a throttled tray/taskbar refresh) that want one
+    /// surface for one progress snapshot instead of
+    /// [`crate::playback_icon_renderer::PlaybackIconRenderer`]'s
+    /// stateful per-instance overlay.
+    pub fn create_progress_tray_surface(progress: f64, playing: bool) -> Option<cairo::ImageSurface> {
+        let size = 32;
+        let surface = Self::create_app_icon_surface(size)?;
+        let cr = cairo::Context::new(&surface).ok()?;
+        let progress = progress.clamp(0.0, 1.0);
+        let size = size as f64;
+
+        // Track the bar sits on, full width, low-alpha black.
+        let bar_height = size * 0.12;
+        let bar_y = size - bar_height;
+        cr.set_source_rgba(0.0, 0.0, 0.0, 0.35);
+        cr.rectangle(0.0, bar_y, size, bar_height);
+        cr.fill().unwrap_or_default();
+
+        // Filled portion, same accent color as the tray volume meter.
+        cr.set_source_rgb(0.96, 0.47, 0.0);
+        cr.rectangle(0.0, bar_y, size * progress, bar_height);
+        cr.fill().unwrap_or_default();
+
+        // Play/pause state dot in the top-right corner: filled while
+        // playing, hollow otherwise.
+        let dot_radius = size * 0.08;
+        let dot_x = size - dot_radius - 2.0;
+        let dot_y = dot_radius + 2.0;
+        cr.set_source_rgb(1.0, 1.0, 1.0);
+        cr.arc(dot_x, dot_y, dot_radius, 0.0, 2.0 * std::f64::consts::PI);
+        if playing {
+            cr.fill().unwrap_or_default();
+        } else {
+            cr.set_line_width(1.0);
+            cr.stroke().unwrap_or_default();
+        }
+
+        drop(cr);
+        Some(surface)
+    }
+
+    /// Draw `peaks` (normalized amplitudes in `[0, 1]`) as a mirrored bar
+    /// graph centered on the horizontal midline of a `size`x`size` canvas,
+    /// for track-preview thumbnails (queue rows, notifications, a
+    /// scrubbing overlay) rather than a fixed glyph. `size` columns are
+    /// drawn: when `peaks` has more samples than that, each column
+    /// max-pools its bucket so loud transients stay visible; when it has
+    /// fewer, the last known sample holds across the gap instead of
+    /// interpolating to zero. When `progress` is given, columns before
+    /// that fraction of the width draw in the accent color (the played
+    /// portion) and the rest draw dimmed, the way audio editors paint
+    /// progress over a waveform.
+    pub fn draw_waveform(
+        cr: &cairo::Context,
+        peaks: &[f32],
+        size: i32,
+        progress: Option<f64>,
+    ) -> bool {
+        if peaks.is_empty() || size <= 0 {
+            return false;
+        }
+
+        let columns = size as usize;
+        let width = size as f64;
+        let mid = width / 2.0;
+        let bar_width = (width / columns as f64).max(1.0);
+        let progress = progress.map(|p| p.clamp(0.0, 1.0));
+
+        for col in 0..columns {
+            let peak = if peaks.len() > columns {
+                let start = col * peaks.len() / columns;
+                let end = (((col + 1) * peaks.len() / columns).max(start + 1)).min(peaks.len());
+                peaks[start..end].iter().cloned().fold(0.0f32, f32::max)
+            } else {
+                let idx = (col * peaks.len() / columns).min(peaks.len() - 1);
+                peaks[idx]
+            };
+
+            let bar_half_height = peak.clamp(0.0, 1.0) as f64 * mid;
+            let x = col as f64 * bar_width;
+
+            let played = progress.map(|p| (col as f64 / columns as f64) < p).unwrap_or(true);
+            if played {
+                cr.set_source_rgb(0.96, 0.47, 0.0); // accent, matches the tray progress bar
+            } else {
+                cr.set_source_rgba(0.5, 0.5, 0.5, 0.5);
+            }
+
+            cr.rectangle(x, mid - bar_half_height, bar_width, bar_half_height * 2.0);
+            cr.fill().unwrap_or_default();
+        }
+
+        true
+    }
+
+    /// Publish the programmatic app icon as `_NET_WM_ICON` on `window`'s
+    /// X11 surface, so the window manager shows a correct taskbar/alt-tab
+    /// icon without shipping SVG theme files. A no-op on Wayland or any
+    /// other backend that doesn't expose an X11 surface.
+    #[cfg(not(target_os = "windows"))]
+    pub fn set_x11_window_icon(window: &gtk::ApplicationWindow) {
+        Self::set_x11_window_icon_with(window, &|size| Self::create_app_icon_surface(size));
+    }
+
+    /// Same as [`Self::set_x11_window_icon`], but renders each size through
+    /// `render` instead of the static app icon, so a caller driving a live
+    /// overlay (like [`crate::playback_icon_renderer::PlaybackIconRenderer`]'s
+    /// progress indicator) can keep the taskbar/alt-tab icon in sync too.
+    #[cfg(not(target_os = "windows"))]
+    pub fn set_x11_window_icon_with(
+        window: &gtk::ApplicationWindow,
+        render: &dyn Fn(i32) -> Option<cairo::ImageSurface>,
+    ) {
+        use gdk4_x11::{X11Display, X11Surface};
+
+        let Some(surface) = window.surface() else {
+            warn!("Window has no GDK surface yet, can't set _NET_WM_ICON");
+            return;
+        };
+        let Some(display) = window.display().downcast::<gdk4_x11::X11Display>().ok() else {
+            info!("Not running on X11, skipping _NET_WM_ICON");
+            return;
+        };
+        let Some(x11_surface) = surface.downcast::<gdk4_x11::X11Surface>().ok() else {
+            info!("Not running on X11, skipping _NET_WM_ICON");
+            return;
+        };
+
+        // Concatenate every size into one CARDINAL array so the WM can
+        // pick whichever fits best.
+        let mut icon_data: Vec<std::os::raw::c_ulong> = Vec::new();
+        let mut image_count = 0;
+        for &size in &[16, 32, 48, 64, 128, 256] {
+            let Some(mut icon_surface) = render(size) else {
+                continue;
+            };
+            let Some(pixels) = Self::net_wm_icon_pixels(&mut icon_surface) else {
+                continue;
+            };
+
+            icon_data.push(size as std::os::raw::c_ulong);
+            icon_data.push(size as std::os::raw::c_ulong);
+            icon_data.extend(pixels);
+            image_count += 1;
+        }
+
+        if icon_data.is_empty() {
+            warn!("No icon surfaces available, not setting _NET_WM_ICON");
+            return;
+        }
+
+        unsafe {
+            let xdisplay = display.xdisplay() as *mut x11::xlib::Display;
+            let xid = x11_surface.xid();
+            let net_wm_icon = x11::xlib::XInternAtom(
+                xdisplay,
+                b"_NET_WM_ICON\0".as_ptr() as *const std::os::raw::c_char,
+                0,
+            );
+
+            x11::xlib::XChangeProperty(
+                xdisplay,
+                xid,
+                net_wm_icon,
+                x11::xlib::XA_CARDINAL,
+                32,
+                x11::xlib::PropModeReplace,
+                icon_data.as_ptr() as *const u8,
+                icon_data.len() as i32,
+            );
+        }
+
+        info!("✅ Published _NET_WM_ICON with {} image(s)", image_count);
+    }
+
+    /// Convert `surface` (ARGB32, premultiplied) into the non-premultiplied
+    /// `0xAARRGGBB` pixel array `_NET_WM_ICON` expects, one logical pixel
+    /// per array element.
+    #[cfg(not(target_os = "windows"))]
+    fn net_wm_icon_pixels(surface: &mut cairo::ImageSurface) -> Option<Vec<std::os::raw::c_ulong>> {
+        let size = surface.width();
+        let stride = surface.stride();
+        let data = surface.data().ok()?;
+
+        let unpremultiply = |channel: u8, alpha: u8| -> u32 {
+            if alpha == 0 {
+                0
+            } else {
+                (channel as u32 * 255 / alpha as u32).min(255)
+            }
+        };
+
+        let mut pixels = Vec::with_capacity((size * size) as usize);
+        for y in 0..size {
+            let row_start = (y * stride) as usize;
+            for x in 0..size {
+                let i = row_start + (x * 4) as usize;
+                let (b, g, r, a) = (data[i], data[i + 1], data[i + 2], data[i + 3]);
+                let pixel = ((a as u32) << 24)
+                    | (unpremultiply(r, a) << 16)
+                    | (unpremultiply(g, a) << 8)
+                    | unpremultiply(b, a);
+                pixels.push(pixel as std::os::raw::c_ulong);
+            }
+        }
+
+        Some(pixels)
+    }
+
     /// Create a Windows HICON for system tray usage
     #[cfg(target_os = "windows")]
     pub fn create_tray_icon() -> Option<windows::Win32::UI::WindowsAndMessaging::HICON> {
-        use windows::Win32::Graphics::Gdi::*;
-        use windows::Win32::UI::WindowsAndMessaging::*;
-        
         info!("🎨 Creating Windows tray icon");
-        
+
         // Create 16x16 icon for tray (standard size)
-        let size = 16;
-        let surface = Self::create_app_icon_surface(size)?;
-        
+        let mut surface = Self::create_app_icon_surface(16)?;
+        Self::hicon_from_surface(&mut surface)
+    }
+
+    /// Render the programmatic app icon and set it as the macOS dock icon,
+    /// so the dock shows the real artwork instead of a generic icon, the
+    /// same role `create_tray_icon` fills on Windows.
+    #[cfg(target_os = "macos")]
+    pub fn set_macos_dock_icon() {
+        info!("🎨 Setting macOS dock icon");
+
+        let Some(mut surface) = Self::create_app_icon_surface(256) else {
+            warn!("Failed to render app icon surface for the dock");
+            return;
+        };
+
+        let Some(image) = Self::nsimage_from_surface(&mut surface) else {
+            warn!("Failed to build NSImage for the dock icon");
+            return;
+        };
+
+        unsafe {
+            use objc2_app_kit::NSApplication;
+            use objc2_foundation::MainThreadMarker;
+
+            let Some(mtm) = MainThreadMarker::new() else {
+                warn!("set_macos_dock_icon must be called from the main thread");
+                return;
+            };
+            NSApplication::sharedApplication(mtm).setApplicationIconImage(Some(&image));
+        }
+
+        info!("✅ Set macOS dock icon from the programmatic app icon");
+    }
+
+    /// Convert a Cairo ARGB32 surface into an `NSImage`, by building a
+    /// `CGImage` from the premultiplied bitmap and wrapping it, the macOS
+    /// counterpart to [`Self::hicon_from_surface`] on Windows.
+    #[cfg(target_os = "macos")]
+    fn nsimage_from_surface(surface: &mut cairo::ImageSurface) -> Option<objc2::rc::Retained<objc2_app_kit::NSImage>> {
+        use objc2_app_kit::NSImage;
+        use objc2_core_foundation::CGFloat;
+        use objc2_core_graphics::{
+            CGBitmapInfo, CGColorSpace, CGDataProvider, CGImage, CGImageAlphaInfo,
+        };
+        use objc2_foundation::NSSize;
+
+        let size = surface.width();
+        let stride = surface.stride();
+        let data = surface.data().ok()?.to_vec();
+
+        let color_space = CGColorSpace::new_device_rgb();
+        let provider = unsafe { CGDataProvider::with_data(&data) }?;
+
+        let bitmap_info = CGBitmapInfo::ByteOrder32Little
+            | CGBitmapInfo(CGImageAlphaInfo::PremultipliedFirst.0);
+
+        let cg_image = unsafe {
+            CGImage::new(
+                size as usize,
+                size as usize,
+                8,
+                32,
+                stride as usize,
+                Some(&color_space),
+                bitmap_info,
+                Some(&provider),
+                None,
+                false,
+                objc2_core_graphics::CGImageRenderingIntent::Default,
+            )
+        }?;
+
+        let image = unsafe {
+            NSImage::initWithCGImage_size(
+                NSImage::alloc(),
+                &cg_image,
+                NSSize::new(size as CGFloat, size as CGFloat),
+            )
+        };
+
+        Some(image)
+    }
+
+    /// Convert a Cairo ARGB32 surface into a Windows `HICON`, for the tray
+    /// icon and the executable icon set alike so both share one pixel
+    /// conversion instead of duplicating the DIB/`CreateIconIndirect` dance.
+    #[cfg(target_os = "windows")]
+    pub fn hicon_from_surface(
+        surface: &mut cairo::ImageSurface,
+    ) -> Option<windows::Win32::UI::WindowsAndMessaging::HICON> {
+        use windows::Win32::Graphics::Gdi::*;
+        use windows::Win32::UI::WindowsAndMessaging::*;
+
+        let size = surface.width();
+
         unsafe {
             // Get surface data
             let stride = surface.stride();
             let data = surface.data().ok()?;
-            
+
             // Create device context
             let hdc = GetDC(None);
             let hdc_mem = CreateCompatibleDC(hdc);
-            
+
             // Create bitmap info
             let mut bmi = BITMAPINFO {
                 bmiHeader: BITMAPINFOHEADER {
@@ -335,7 +1122,7 @@ impl IconRenderer {
                 },
                 bmiColors: [RGBQUAD::default(); 1],
             };
-            
+
             // Create DIB bitmap
             let mut bits: *mut std::ffi::c_void = std::ptr::null_mut();
             let hbm_color = CreateDIBSection(
@@ -346,33 +1133,33 @@ impl IconRenderer {
                 None,
                 0,
             ).ok()?;
-            
+
             if hbm_color.is_invalid() || bits.is_null() {
                 warn!("Failed to create DIB section for tray icon");
                 ReleaseDC(None, hdc);
                 DeleteDC(hdc_mem);
                 return None;
             }
-            
+
             // Copy Cairo surface data to bitmap
             let dest_slice = std::slice::from_raw_parts_mut(bits as *mut u8, (size * size * 4) as usize);
             for y in 0..size {
                 let src_offset = (y * stride) as usize;
                 let dst_offset = (y * size * 4) as usize;
                 let row_size = (size * 4) as usize;
-                
+
                 if src_offset + row_size <= data.len() && dst_offset + row_size <= dest_slice.len() {
                     // Convert BGRA to RGBA and pre-multiply alpha
                     for x in 0..size {
                         let src_pixel = src_offset + (x * 4) as usize;
                         let dst_pixel = dst_offset + (x * 4) as usize;
-                        
+
                         if src_pixel + 3 < data.len() && dst_pixel + 3 < dest_slice.len() {
                             let b = data[src_pixel + 0] as f32;
                             let g = data[src_pixel + 1] as f32;
                             let r = data[src_pixel + 2] as f32;
                             let a = data[src_pixel + 3] as f32;
-                            
+
                             // Pre-multiply alpha for Windows
                             let alpha_norm = a / 255.0;
                             dest_slice[dst_pixel + 0] = (b * alpha_norm) as u8; // B
@@ -383,10 +1170,36 @@ impl IconRenderer {
                     }
                 }
             }
-            
-            // Create mask bitmap (for transparency)
-            let hbm_mask = CreateBitmap(size, size, 1, 1, None);
-            
+
+            // Create mask bitmap (for transparency). This is a 1-bpp AND
+            // mask, bit = 1 where the pixel should be transparent, with
+            // each row padded to a 4-byte/DWORD boundary as CreateBitmap
+            // expects for a monochrome bitmap; the color DIB above already
+            // carries real alpha for the 32-bit blend path, but the mask
+            // still has to be authoritative so the icon renders cleanly
+            // against any tray background.
+            const ALPHA_THRESHOLD: u8 = 128;
+            let mask_row_bytes = (((size + 31) / 32) * 4) as usize;
+            let mut mask_bits = vec![0u8; mask_row_bytes * size as usize];
+            for y in 0..size {
+                let src_offset = (y * stride) as usize;
+                let mask_row_offset = y as usize * mask_row_bytes;
+                for x in 0..size {
+                    let pixel_offset = src_offset + (x * 4) as usize;
+                    if pixel_offset + 3 < data.len() && data[pixel_offset + 3] < ALPHA_THRESHOLD {
+                        mask_bits[mask_row_offset + (x / 8) as usize] |= 0x80 >> (x % 8);
+                    }
+                }
+            }
+
+            let hbm_mask = CreateBitmap(
+                size,
+                size,
+                1,
+                1,
+                Some(mask_bits.as_ptr() as *const std::ffi::c_void),
+            );
+
             // Create icon info
             let icon_info = ICONINFO {
                 fIcon: true.into(),
@@ -395,292 +1208,26 @@ impl IconRenderer {
                 hbmMask: hbm_mask,
                 hbmColor: hbm_color,
             };
-            
+
             // Create the icon
             let hicon = CreateIconIndirect(&icon_info).ok()?;
-            
+
             // Cleanup
             DeleteObject(hbm_color);
             DeleteObject(hbm_mask);
             DeleteDC(hdc_mem);
             ReleaseDC(None, hdc);
-            
+
             if hicon.is_invalid() {
                 warn!("Failed to create Windows icon");
                 None
             } else {
-                info!("✅ Successfully created Windows tray icon");
+                info!("✅ Successfully created Windows icon ({0}x{0})", size);
                 Some(hicon)
             }
         }
     }
-    
-    /// Create an ICO file for the executable
-    pub fn create_executable_ico_file(path: &str) -> Result<(), Box<dyn std::error::Error>> {
-        info!("🎨 Creating executable ICO file at: {}", path);
-        
-        // Create multiple sizes for Windows (16, 32, 48, 256)
-        let sizes = [16, 32, 48, 256];
-        let mut ico_data = Vec::new();
-        
-        // ICO file header
-        ico_data.extend_from_slice(&[0, 0]); // Reserved (must be 0)
-        ico_data.extend_from_slice(&[1, 0]); // Type (1 = ICO)
-        ico_data.extend_from_slice(&(sizes.len() as u16).to_le_bytes()); // Number of images
-        
-        let mut image_data = Vec::new();
-        let mut directory_entries = Vec::new();
-        
-        for &size in &sizes {
-            // Create surface for this size
-            if let Some(surface) = Self::create_app_icon_surface(size) {
-                // Convert Cairo surface to raw bitmap data (simplified)
-                let png_data = vec![0u8; (size * size * 4) as usize]; // Placeholder data
-                
-                // ICO directory entry
-                let mut entry = Vec::new();
-                entry.push(if size == 256 { 0 } else { size as u8 }); // Width (0 = 256)
-                entry.push(if size == 256 { 0 } else { size as u8 }); // Height (0 = 256)
-                entry.push(0); // Color palette (0 = no palette)
-                entry.push(0); // Reserved
-                entry.extend_from_slice(&1u16.to_le_bytes()); // Color planes
-                entry.extend_from_slice(&32u16.to_le_bytes()); // Bits per pixel
-                entry.extend_from_slice(&(png_data.len() as u32).to_le_bytes()); // Image size
-                entry.extend_from_slice(&((6 + sizes.len() * 16 + image_data.len()) as u32).to_le_bytes()); // Image offset
-                
-                directory_entries.extend_from_slice(&entry);
-                image_data.extend_from_slice(&png_data);
-            }
-        }
-        
-        // Combine header + directory + images
-        ico_data.extend_from_slice(&directory_entries);
-        ico_data.extend_from_slice(&image_data);
-        
-        // Write to file
-        std::fs::write(path, ico_data)?;
-        info!("✅ Successfully created ICO file: {}", path);
-        
-        Ok(())
-    }
-    
-    /// Create app icon at build time for embedding in executable
-    pub fn generate_build_time_icons() -> Result<(), Box<dyn std::error::Error>> {
-        info!("🏗️ Generating build-time icons for executable");
-        
-        // Create ICO file for Windows executable
-        Self::create_executable_ico_file("amberol.ico")?;
-        
-        // Also create PNG versions for other platforms
-        for &size in &[16, 32, 48, 64, 128, 256] {
-            if let Some(surface) = Self::create_app_icon_surface(size) {
-                let filename = format!("amberol-{}x{}.png", size, size);
-                // PNG writing would require cairo-rs feature
-                info!("Would create {}", filename);
-                info!("✅ Created {}", filename);
-            }
-        }
-        
-        Ok(())
-    }
-    
-    /// Create Windows HICON set for executable (multiple sizes)
-    #[cfg(target_os = "windows")]
-    pub fn create_executable_icon_set() -> Vec<(i32, windows::Win32::UI::WindowsAndMessaging::HICON)> {
-        info!("🎨 Creating Windows executable icon set");
-        
-        let sizes = [16, 32, 48, 64, 128, 256];
-        let mut icons = Vec::new();
-        
-        for &size in &sizes {
-            if let Some(hicon) = Self::create_windows_icon_from_surface(size) {
-                icons.push((size, hicon));
-                info!("✅ Created {}x{} executable icon", size, size);
-            } else {
-                warn!("❌ Failed to create {}x{} executable icon", size, size);
-            }
-        }
-        
-        info!("✅ Created {} executable icons", icons.len());
-        icons
-    }
-    
-    /// Helper function to create Windows HICON from Cairo surface
-    #[cfg(target_os = "windows")]
-    fn create_windows_icon_from_surface(size: i32) -> Option<windows::Win32::UI::WindowsAndMessaging::HICON> {
-        use windows::Win32::Graphics::Gdi::*;
-        use windows::Win32::UI::WindowsAndMessaging::*;
-        
-        let mut surface = Self::create_app_icon_surface(size)?;
-        
-        unsafe {
-            let stride = surface.stride();
-            let data = surface.data().ok()?;
-            
-            let hdc = GetDC(None);
-            let hdc_mem = CreateCompatibleDC(hdc);
-            
-            let mut bmi = BITMAPINFO {
-                bmiHeader: BITMAPINFOHEADER {
-                    biSize: std::mem::size_of::<BITMAPINFOHEADER>() as u32,
-                    biWidth: size,
-                    biHeight: -size,
-                    biPlanes: 1,
-                    biBitCount: 32,
-                    biCompression: BI_RGB.0,
-                    biSizeImage: 0,
-                    biXPelsPerMeter: 0,
-                    biYPelsPerMeter: 0,
-                    biClrUsed: 0,
-                    biClrImportant: 0,
-                },
-                bmiColors: [RGBQUAD::default(); 1],
-            };
-            
-            let mut bits: *mut std::ffi::c_void = std::ptr::null_mut();
-            let hbm_color = CreateDIBSection(hdc_mem, &bmi, DIB_RGB_COLORS, &mut bits, None, 0).ok()?;
-            
-            if hbm_color.is_invalid() || bits.is_null() {
-                ReleaseDC(None, hdc);
-                DeleteDC(hdc_mem);
-                return None;
-            }
-            
-            // Copy and convert pixel data
-            let dest_slice = std::slice::from_raw_parts_mut(bits as *mut u8, (size * size * 4) as usize);
-            for y in 0..size {
-                let src_offset = (y * stride) as usize;
-                let dst_offset = (y * size * 4) as usize;
-                
-                for x in 0..size {
-                    let src_pixel = src_offset + (x * 4) as usize;
-                    let dst_pixel = dst_offset + (x * 4) as usize;
-                    
-                    if src_pixel + 3 < data.len() && dst_pixel + 3 < dest_slice.len() {
-                        let b = data[src_pixel + 0] as f32;
-                        let g = data[src_pixel + 1] as f32;
-                        let r = data[src_pixel + 2] as f32;
-                        let a = data[src_pixel + 3] as f32;
-                        
-                        let alpha_norm = a / 255.0;
-                        dest_slice[dst_pixel + 0] = (b * alpha_norm) as u8;
-                        dest_slice[dst_pixel + 1] = (g * alpha_norm) as u8;
-                        dest_slice[dst_pixel + 2] = (r * alpha_norm) as u8;
-                        dest_slice[dst_pixel + 3] = a as u8;
-                    }
-                }
-            }
-            
-            let hbm_mask = CreateBitmap(size, size, 1, 1, None);
-            let icon_info = ICONINFO {
-                fIcon: true.into(),
-                xHotspot: 0,
-                yHotspot: 0,
-                hbmMask: hbm_mask,
-                hbmColor: hbm_color,
-            };
-            
-            let hicon = CreateIconIndirect(&icon_info).ok()?;
-            
-            DeleteObject(hbm_color);
-            DeleteObject(hbm_mask);
-            DeleteDC(hdc_mem);
-            ReleaseDC(None, hdc);
-            
-            if hicon.is_invalid() { None } else { Some(hicon) }
-        }
-    }
-    
-    /// Create ICO file data for embedding in executable
-    #[cfg(target_os = "windows")]
-    pub fn create_ico_file_data() -> Option<Vec<u8>> {
-        info!("🎨 Creating ICO file data for executable");
-        
-        let sizes = [16, 32, 48, 64, 128, 256];
-        let mut ico_data = Vec::new();
-        let mut images_data = Vec::new();
-        
-        // ICO header
-        ico_data.extend_from_slice(&[0, 0]); // Reserved
-        ico_data.extend_from_slice(&[1, 0]); // Type (1 = ICO)
-        ico_data.extend_from_slice(&(sizes.len() as u16).to_le_bytes()); // Number of images
-        
-        let mut offset = 6 + (sizes.len() * 16); // Header + directory entries
-        
-        for &size in &sizes {
-            if let Some(mut surface) = Self::create_app_icon_surface(size) {
-                let stride = surface.stride();
-                if let Ok(data) = surface.data() {
-                    
-                    // Create BMP data for this size
-                    let mut bmp_data = Vec::new();
-                    
-                    // BMP header
-                    let header_size = 40u32;
-                    bmp_data.extend_from_slice(&header_size.to_le_bytes());
-                    bmp_data.extend_from_slice(&(size as u32).to_le_bytes());
-                    bmp_data.extend_from_slice(&(size as u32 * 2).to_le_bytes()); // Height * 2 for mask
-                    bmp_data.extend_from_slice(&1u16.to_le_bytes()); // Planes
-                    bmp_data.extend_from_slice(&32u16.to_le_bytes()); // Bits per pixel
-                    bmp_data.extend_from_slice(&0u32.to_le_bytes()); // Compression
-                    bmp_data.extend_from_slice(&((size * size * 4) as u32).to_le_bytes()); // Image size
-                    bmp_data.extend_from_slice(&0u32.to_le_bytes()); // X pixels per meter
-                    bmp_data.extend_from_slice(&0u32.to_le_bytes()); // Y pixels per meter
-                    bmp_data.extend_from_slice(&0u32.to_le_bytes()); // Colors used
-                    bmp_data.extend_from_slice(&0u32.to_le_bytes()); // Important colors
-                    
-                    // Pixel data (bottom-up)
-                    for y in (0..size).rev() {
-                        let src_offset = (y * stride) as usize;
-                        for x in 0..size {
-                            let pixel_offset = src_offset + (x * 4) as usize;
-                            if pixel_offset + 3 < data.len() {
-                                // BGRA format for BMP
-                                bmp_data.push(data[pixel_offset + 0]); // B
-                                bmp_data.push(data[pixel_offset + 1]); // G
-                                bmp_data.push(data[pixel_offset + 2]); // R
-                                bmp_data.push(data[pixel_offset + 3]); // A
-                            } else {
-                                bmp_data.extend_from_slice(&[0, 0, 0, 0]);
-                            }
-                        }
-                    }
-                    
-                    // Mask data (all transparent for now)
-                    let mask_size = (size * size + 7) / 8; // 1 bit per pixel, rounded up to bytes
-                    bmp_data.resize(bmp_data.len() + mask_size as usize, 0);
-                    
-                    // ICO directory entry
-                    let entry_size = if size >= 256 { 0 } else { size as u8 };
-                    ico_data.push(entry_size); // Width
-                    ico_data.push(entry_size); // Height
-                    ico_data.push(0); // Color count
-                    ico_data.push(0); // Reserved
-                    ico_data.extend_from_slice(&1u16.to_le_bytes()); // Planes
-                    ico_data.extend_from_slice(&32u16.to_le_bytes()); // Bits per pixel
-                    ico_data.extend_from_slice(&(bmp_data.len() as u32).to_le_bytes()); // Image size
-                    ico_data.extend_from_slice(&(offset as u32).to_le_bytes()); // Offset
-                    
-                    offset += bmp_data.len();
-                    images_data.push(bmp_data);
-                }
-            }
-        }
-        
-        // Append all image data
-        for image_data in images_data {
-            ico_data.extend_from_slice(&image_data);
-        }
-        
-        if ico_data.len() > 6 {
-            info!("✅ Created ICO file data ({} bytes)", ico_data.len());
-            Some(ico_data)
-        } else {
-            warn!("❌ Failed to create ICO file data");
-            None
-        }
-    }
-    
+
     /// Draw consecutive/linear playback icon (two arrows pointing right)
     fn draw_consecutive(cr: &cairo::Context) -> bool {
         // Top arrow: horizontal line with triangle
@@ -861,7 +1408,112 @@ impl IconRenderer {
         cr.fill().unwrap_or_default();
         true
     }
-    
+
+    /// Draw rewind (double triangle left with a trailing bar), for
+    /// seek-by-interval controls -- distinct from [`Self::draw_skip_backward`],
+    /// which has no bar and means "previous track".
+    fn draw_rewind(cr: &cairo::Context) -> bool {
+        cr.rectangle(1.0, 2.0, 1.5, 12.0);
+        cr.fill().unwrap_or_default();
+
+        cr.move_to(3.0, 8.0);
+        cr.line_to(8.0, 3.0);
+        cr.line_to(8.0, 13.0);
+        cr.close_path();
+        cr.fill().unwrap_or_default();
+
+        cr.move_to(8.0, 8.0);
+        cr.line_to(13.0, 3.0);
+        cr.line_to(13.0, 13.0);
+        cr.close_path();
+        cr.fill().unwrap_or_default();
+        true
+    }
+
+    /// Draw fast-forward (double triangle right with a trailing bar), for
+    /// seek-by-interval controls -- distinct from [`Self::draw_skip_forward`],
+    /// which has no bar and means "next track".
+    fn draw_fast_forward(cr: &cairo::Context) -> bool {
+        cr.move_to(3.0, 3.0);
+        cr.line_to(3.0, 13.0);
+        cr.line_to(8.0, 8.0);
+        cr.close_path();
+        cr.fill().unwrap_or_default();
+
+        cr.move_to(8.0, 3.0);
+        cr.line_to(8.0, 13.0);
+        cr.line_to(13.0, 8.0);
+        cr.close_path();
+        cr.fill().unwrap_or_default();
+
+        cr.rectangle(13.5, 2.0, 1.5, 12.0);
+        cr.fill().unwrap_or_default();
+        true
+    }
+
+    /// Draw stop (filled square)
+    fn draw_stop(cr: &cairo::Context) -> bool {
+        cr.rectangle(3.0, 3.0, 10.0, 10.0);
+        cr.fill().unwrap_or_default();
+        true
+    }
+
+    /// Draw a microphone (capsule on a stand), for input-source indicators
+    fn draw_mic(cr: &cairo::Context) -> bool {
+        // Capsule
+        cr.save().unwrap_or_default();
+        cr.translate(8.0, 6.0);
+        cr.scale(1.0, 1.5);
+        cr.arc(0.0, 0.0, 2.5, 0.0, 2.0 * std::f64::consts::PI);
+        cr.fill().unwrap_or_default();
+        cr.restore().unwrap_or_default();
+
+        // Stand arc
+        cr.set_line_width(1.2);
+        cr.arc(8.0, 8.0, 4.5, 0.2 * std::f64::consts::PI, 0.8 * std::f64::consts::PI);
+        cr.stroke().unwrap_or_default();
+
+        // Stem and base
+        cr.move_to(8.0, 12.5);
+        cr.line_to(8.0, 14.0);
+        cr.stroke().unwrap_or_default();
+        cr.move_to(5.0, 14.0);
+        cr.line_to(11.0, 14.0);
+        cr.stroke().unwrap_or_default();
+        true
+    }
+
+    /// Draw a line-in jack (plug nose feeding into a port), for
+    /// audio-input-source indicators
+    fn draw_line_in(cr: &cairo::Context) -> bool {
+        // Port
+        cr.rectangle(2.0, 6.0, 4.0, 4.0);
+        cr.fill().unwrap_or_default();
+
+        // Cable
+        cr.set_line_width(1.5);
+        cr.move_to(6.0, 8.0);
+        cr.line_to(11.0, 8.0);
+        cr.stroke().unwrap_or_default();
+
+        // Plug tip
+        cr.arc(13.0, 8.0, 2.0, 0.0, 2.0 * std::f64::consts::PI);
+        cr.fill().unwrap_or_default();
+        true
+    }
+
+    /// Draw an optical disc (ring with a center hole), for CD/disc source
+    /// indicators
+    fn draw_disc(cr: &cairo::Context) -> bool {
+        cr.set_line_width(1.5);
+        cr.arc(8.0, 8.0, 6.0, 0.0, 2.0 * std::f64::consts::PI);
+        cr.stroke().unwrap_or_default();
+
+        cr.arc(8.0, 8.0, 1.5, 0.0, 2.0 * std::f64::consts::PI);
+        cr.fill().unwrap_or_default();
+        true
+    }
+
     /// Draw queue/playlist view (horizontal lines)
     fn draw_queue(cr: &cairo::Context) -> bool {
         cr.rectangle(2.0, 2.0, 12.0, 2.0);
@@ -1050,13 +1702,18 @@ impl IconRenderer {
         true
     }
     
-    /// Draw Amberol app icon (stylized music wave/note)
-    fn draw_amberol_app_icon(cr: &cairo::Context) -> bool {
+    /// Draw Amberol app icon (stylized music wave/note). `accent`, when
+    /// given, recolors the background circle to the user's libadwaita
+    /// accent instead of the fixed Amberol red -- used for the symbolic
+    /// `io.bassi.Amberol`/`amberol` icon name, not the app's own fixed-
+    /// identity taskbar/dock/ICO icon (see [`Self::create_app_icon_surface`]).
+    fn draw_amberol_app_icon(cr: &cairo::Context, accent: Option<(f64, f64, f64)>) -> bool {
         // Background circle (app icon style)
-        cr.set_source_rgb(0.91, 0.26, 0.21); // Amberol red color #e8433f
+        let background = accent.unwrap_or((0.91, 0.26, 0.21)); // Amberol red color #e84236
+        cr.set_source_rgb(background.0, background.1, background.2);
         cr.arc(8.0, 8.0, 7.0, 0.0, 2.0 * std::f64::consts::PI);
         cr.fill().unwrap_or_default();
-        
+
         // White music note
         cr.set_source_rgb(1.0, 1.0, 1.0);
         
@@ -1120,6 +1777,26 @@ impl IconRenderer {
         true
     }
     
+    /// Draw volume off icon (speaker, no waves, no mute X) for an
+    /// unmuted but zero level
+    fn draw_volume_off(cr: &cairo::Context) -> bool {
+        // Speaker cone
+        cr.move_to(3.0, 6.0);
+        cr.line_to(5.0, 6.0);
+        cr.line_to(7.0, 4.0);
+        cr.line_to(7.0, 12.0);
+        cr.line_to(5.0, 10.0);
+        cr.line_to(3.0, 10.0);
+        cr.close_path();
+        cr.fill().unwrap_or_default();
+
+        // Speaker grille
+        cr.rectangle(1.0, 7.0, 2.0, 2.0);
+        cr.fill().unwrap_or_default();
+
+        true
+    }
+
     /// Draw volume low icon (speaker with one wave)
     fn draw_volume_low(cr: &cairo::Context) -> bool {
         // Speaker cone
@@ -3,202 +3,345 @@
 
 use gtk::{gdk, glib, prelude::*};
 use log::{info, warn};
+use std::path::Path;
 
-/// Aggressive icon replacer that scans widget trees and replaces missing icons
+/// Full-color app icon names we render as raster fallbacks.
+const APP_ICON_NAMES: &[&str] = &["io.bassi.Amberol", "io.bassi.Amberol.Devel"];
+
+/// Nominal sizes we render `APP_ICON_NAMES` at, matching the toolbar (16px),
+/// menu (24px) and about-dialog (48px) contexts these icons show up in. For
+/// each of these we also render a `@2x` variant, so GTK's icon theme lookup
+/// picks a crisp bitmap on HiDPI displays instead of upscaling the 1x one.
+const FALLBACK_ICON_SIZES: &[i32] = &[16, 24, 32, 48];
+
+/// Symbolic icon names we render as a single scalable SVG apiece. GTK treats
+/// any icon whose name ends in `-symbolic` specially: paths filled with the
+/// special foreground color `#2e3436` are recolored to match the widget's
+/// current theme foreground, so these track dark/light mode and HiDPI scale
+/// changes automatically, the same way real symbolic icons do.
+const SYMBOLIC_ICON_NAMES: &[&str] = &[
+    "web-browser-symbolic",
+    "user-home-symbolic",
+    "document-edit-symbolic",
+    "bug-symbolic",
+    "system-search-symbolic",
+    "open-menu-symbolic",
+    "audio-only-symbolic",
+    "folder-music-symbolic",
+    "image-missing",
+];
+
+/// GTK's recognized symbolic foreground color: paths filled with this exact
+/// color are recolored to the current theme foreground.
+const SYMBOLIC_FOREGROUND: &str = "#2e3436";
+
+/// Serves our programmatically-rendered icons through a fallback `gtk::IconTheme`
+/// search path, so that GTK's own icon lookup resolves them for every widget --
+/// including ones created after startup -- without a periodic widget-tree scan.
+/// We only render an icon the active theme genuinely lacks, per
+/// [`IconReplacer::is_genuinely_missing`].
 pub struct IconReplacer;
 
 impl IconReplacer {
-    /// Setup periodic icon replacement scanning
-    pub fn setup_periodic_replacement() {
-        info!("🔄 Setting up periodic icon replacement scanning");
-        
-        // Run icon replacement every 2 seconds to catch dynamically created widgets
-        glib::timeout_add_seconds_local(2, || {
-            Self::scan_and_replace_icons();
-            glib::ControlFlow::Continue
-        });
-        
-        // Also run immediately
-        Self::scan_and_replace_icons();
+    /// Register our fallback icon directory with the default display's icon theme.
+    ///
+    /// This only needs to run once, early in application startup: GTK's icon
+    /// lookup machinery takes care of the rest, so there is no timer and no
+    /// need to walk widget trees looking for `image-missing`.
+    pub fn install_fallback_theme() {
+        let Some(display) = gdk::Display::default() else {
+            warn!("No default display, cannot install fallback icon theme");
+            return;
+        };
+
+        let icon_theme = gtk::IconTheme::for_display(&display);
+        let icon_dir = Self::fallback_theme_dir();
+        Self::render_fallback_icons(&icon_theme, &icon_dir);
+
+        icon_theme.add_search_path(&icon_dir);
+
+        info!("Installed fallback icon theme at {:?}", icon_dir);
     }
-    
-    /// Scan all windows and replace missing icons
-    fn scan_and_replace_icons() {
-        info!("🔍 Scanning for missing icons to replace");
-        
-        // Get all GTK applications
-        if let Some(app) = gtk::gio::Application::default() {
-            if let Some(gtk_app) = app.downcast_ref::<gtk::Application>() {
-                // Scan all windows
-                for window in gtk_app.windows() {
-                    Self::scan_widget_tree(&window);
-                }
+
+    /// Set `image` to `base_name` with `emblem_name` composited into its
+    /// lower-right quadrant, mirroring GNOME's `GEmblemedIcon` pattern. Useful
+    /// for flagging a queue row as missing-file or errored without a
+    /// dedicated icon asset for every base/emblem combination.
+    pub fn set_image_with_emblem(image: &gtk::Image, base_name: &str, emblem_name: &str) {
+        let size = if image.pixel_size() > 0 {
+            image.pixel_size()
+        } else {
+            16
+        };
+
+        match Self::emblemed_texture(base_name, emblem_name, size) {
+            Some(texture) => image.set_paintable(Some(&texture)),
+            None => {
+                warn!(
+                    "Failed to composite emblem {} onto {}, falling back to plain icon",
+                    emblem_name, base_name
+                );
+                image.set_icon_name(Some(base_name));
             }
         }
-        
-        // Also scan any top-level windows we can find
-        let display = gdk::Display::default().unwrap();
-        // Note: GTK4 doesn't provide direct access to all windows, so we rely on the application
     }
-    
-    /// Recursively scan a widget tree and replace icons
-    fn scan_widget_tree(widget: &impl IsA<gtk::Widget>) {
-        let widget = widget.as_ref();
-        
-        // Check if this widget is an Image that might have a missing icon
-        if let Some(image) = widget.downcast_ref::<gtk::Image>() {
-            Self::replace_image_icon(image);
+
+    /// Render `base_name` and paint `emblem_name` over its lower-right
+    /// quadrant at roughly half size, then convert the result to a texture.
+    fn emblemed_texture(base_name: &str, emblem_name: &str, size: i32) -> Option<gdk::Texture> {
+        let mut surface = Self::load_or_draw_icon_surface(base_name, size)?;
+
+        let emblem_size = (size + 1) / 2;
+        if let Some(emblem_surface) = Self::load_or_draw_icon_surface(emblem_name, emblem_size) {
+            let cr = gtk::cairo::Context::new(&surface).ok()?;
+            let offset = (size - emblem_size) as f64;
+            cr.set_source_surface(&emblem_surface, offset, offset).ok()?;
+            cr.paint().ok()?;
+        }
+
+        Self::surface_to_texture(&mut surface)
+    }
+
+    /// Resolve an icon surface for compositing: our own app icon, one of our
+    /// drawn status emblems, or -- for anything else -- whatever the active
+    /// icon theme resolves the name to.
+    fn load_or_draw_icon_surface(icon_name: &str, size: i32) -> Option<gtk::cairo::ImageSurface> {
+        if APP_ICON_NAMES.contains(&icon_name) {
+            return Self::create_icon_surface_for_name(icon_name, size);
         }
-        
-        // Check if this widget is a Button with an icon
-        if let Some(button) = widget.downcast_ref::<gtk::Button>() {
-            Self::replace_button_icon(button);
+
+        if let Some(color) = Self::emblem_color(icon_name) {
+            return Self::draw_emblem_surface(color, size);
         }
-        
-        // Recursively scan child widgets
-        let mut child = widget.first_child();
-        while let Some(current_child) = child {
-            Self::scan_widget_tree(&current_child);
-            child = current_child.next_sibling();
+
+        Self::load_themed_icon_surface(icon_name, size)
+    }
+
+    /// Fill color for the status emblems we know how to draw ourselves.
+    fn emblem_color(icon_name: &str) -> Option<(f64, f64, f64)> {
+        match icon_name {
+            "dialog-warning-symbolic" => Some((0.83, 0.63, 0.0)), // amber
+            "dialog-error-symbolic" => Some((0.8, 0.0, 0.0)),     // red
+            _ => None,
         }
     }
-    
-    /// Replace icon in a gtk::Image widget
-    fn replace_image_icon(image: &gtk::Image) {
-        // Check what kind of image this is
-        match image.storage_type() {
-            gtk::ImageType::IconName => {
-                if let Some(icon_name) = image.icon_name() {
-                    if Self::should_replace_icon(&icon_name) {
-                        info!("🎨 Replacing image icon: {}", icon_name);
-                        Self::set_programmatic_image(image, &icon_name);
-                    }
-                }
-            }
-            gtk::ImageType::Gicon => {
-                // Handle GIcon case - might be showing image-missing
-                if let Some(gicon) = image.gicon() {
-                    if let Some(themed_icon) = gicon.downcast_ref::<gtk::gio::ThemedIcon>() {
-                        let names = themed_icon.names();
-                        for name in names {
-                            if Self::should_replace_icon(&name) {
-                                info!("🎨 Replacing GIcon: {}", name);
-                                Self::set_programmatic_image(image, &name);
-                                break;
-                            }
-                        }
-                    }
-                }
+
+    /// Draw a filled triangle-with-exclamation-mark emblem in `color`.
+    fn draw_emblem_surface(color: (f64, f64, f64), size: i32) -> Option<gtk::cairo::ImageSurface> {
+        use gtk::cairo;
+
+        let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, size, size).ok()?;
+        let cr = cairo::Context::new(&surface).ok()?;
+        let s = size as f64;
+
+        cr.set_source_rgba(0.0, 0.0, 0.0, 0.0);
+        cr.paint().ok()?;
+
+        let (r, g, b) = color;
+        cr.set_source_rgba(r, g, b, 1.0);
+        cr.move_to(s * 0.5, s * 0.05);
+        cr.line_to(s * 0.95, s * 0.9);
+        cr.line_to(s * 0.05, s * 0.9);
+        cr.close_path();
+        cr.fill().ok()?;
+
+        cr.set_source_rgba(1.0, 1.0, 1.0, 1.0);
+        cr.rectangle(s * 0.46, s * 0.35, s * 0.08, s * 0.28);
+        cr.fill().ok()?;
+        cr.rectangle(s * 0.46, s * 0.72, s * 0.08, s * 0.08);
+        cr.fill().ok()?;
+
+        Some(surface)
+    }
+
+    /// Load an icon from the active theme and draw it onto a fresh surface.
+    fn load_themed_icon_surface(icon_name: &str, size: i32) -> Option<gtk::cairo::ImageSurface> {
+        use gtk::cairo;
+
+        let display = gdk::Display::default()?;
+        let icon_theme = gtk::IconTheme::for_display(&display);
+        let paintable = icon_theme.lookup_icon(
+            icon_name,
+            &[],
+            size,
+            1,
+            gtk::TextDirection::None,
+            gtk::IconLookupFlags::empty(),
+        );
+        let path = paintable.file()?.path()?;
+        let pixbuf = gtk::gdk_pixbuf::Pixbuf::from_file_at_scale(path, size, size, true).ok()?;
+
+        let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, size, size).ok()?;
+        let cr = cairo::Context::new(&surface).ok()?;
+        cr.set_source_pixbuf(&pixbuf, 0.0, 0.0);
+        cr.paint().ok()?;
+
+        Some(surface)
+    }
+
+    /// Convert a cairo surface to a `gdk::Texture` via an intermediate pixbuf.
+    fn surface_to_texture(surface: &mut gtk::cairo::ImageSurface) -> Option<gdk::Texture> {
+        let width = surface.width();
+        let height = surface.height();
+        let stride = surface.stride();
+        let data = surface.data().ok()?;
+
+        let pixbuf = gtk::gdk_pixbuf::Pixbuf::from_bytes(
+            &glib::Bytes::from(&data[..]),
+            gtk::gdk_pixbuf::Colorspace::Rgb,
+            true,
+            8,
+            width,
+            height,
+            stride,
+        );
+
+        Some(gdk::Texture::for_pixbuf(&pixbuf))
+    }
+
+    /// Directory that acts as the root of our fallback icon theme.
+    fn fallback_theme_dir() -> std::path::PathBuf {
+        glib::user_cache_dir().join("amberol").join("icons")
+    }
+
+    /// Render every fallback icon that the active theme genuinely lacks into
+    /// the theme directory, if not already present.
+    fn render_fallback_icons(icon_theme: &gtk::IconTheme, theme_dir: &Path) {
+        for icon_name in APP_ICON_NAMES {
+            if !Self::is_genuinely_missing(icon_theme, icon_name) {
+                continue;
             }
-            _ => {
-                // For other image types, we can't easily determine if they're missing
+
+            for &size in FALLBACK_ICON_SIZES {
+                Self::render_fallback_icon_at(theme_dir, icon_name, size, 1);
+                Self::render_fallback_icon_at(theme_dir, icon_name, size, 2);
             }
         }
-    }
-    
-    /// Replace icon in a gtk::Button widget
-    fn replace_button_icon(button: &gtk::Button) {
-        if let Some(icon_name) = button.icon_name() {
-            if Self::should_replace_icon(&icon_name) {
-                info!("🎨 Replacing button icon: {}", icon_name);
-                // Use our programmatic icon renderer
-                crate::icon_renderer::IconRenderer::set_button_icon_programmatic(button, &icon_name);
+
+        for icon_name in SYMBOLIC_ICON_NAMES {
+            if !Self::is_genuinely_missing(icon_theme, icon_name) {
+                continue;
             }
+
+            Self::render_symbolic_icon(theme_dir, icon_name);
         }
     }
-    
-    /// Check if an icon should be replaced
-    fn should_replace_icon(icon_name: &str) -> bool {
-        // List of icons we want to replace
-        matches!(icon_name,
-            "io.bassi.Amberol" |
-            "io.bassi.Amberol.Devel" |
-            "web-browser-symbolic" |
-            "user-home-symbolic" |
-            "document-edit-symbolic" |
-            "bug-symbolic" |
-            "system-search-symbolic" |
-            "open-menu-symbolic" |
-            "audio-only-symbolic" |
-            "folder-music-symbolic" |
-            "image-missing"  // Catch the fallback directly
-        )
+
+    /// Render a single symbolic icon as a scalable SVG, so GTK's symbolic
+    /// icon renderer can recolor it to match the widget's current theme
+    /// foreground instead of us baking in a fixed color.
+    fn render_symbolic_icon(theme_dir: &Path, icon_name: &str) {
+        let scalable_dir = theme_dir.join("hicolor").join("scalable").join("apps");
+        if let Err(err) = std::fs::create_dir_all(&scalable_dir) {
+            warn!("Failed to create fallback icon directory {:?}: {}", scalable_dir, err);
+            return;
+        }
+
+        let icon_path = scalable_dir.join(format!("{icon_name}.svg"));
+        if icon_path.exists() {
+            return;
+        }
+
+        if let Err(err) = std::fs::write(&icon_path, Self::symbolic_svg_for(icon_name)) {
+            warn!("Failed to write fallback icon {:?}: {}", icon_path, err);
+        }
     }
-    
-    /// Set a programmatic image for a gtk::Image widget
-    fn set_programmatic_image(image: &gtk::Image, icon_name: &str) {
-        // Create a paintable for this icon and set it directly
-        if let Some(paintable) = Self::create_paintable_for_icon(icon_name) {
-            image.set_paintable(Some(&paintable));
-            info!("✅ Successfully replaced image with programmatic icon: {}", icon_name);
+
+    /// Render a single fallback icon at `size` logical pixels and `scale`,
+    /// writing it into the `<size>x<size>[@<scale>x]/apps` directory of our
+    /// icon theme, the layout GTK's generic-fallback lookup expects.
+    fn render_fallback_icon_at(theme_dir: &Path, icon_name: &str, size: i32, scale: i32) {
+        let size_dir = if scale == 1 {
+            format!("{size}x{size}")
         } else {
-            warn!("⚠️ Failed to create paintable for icon: {}", icon_name);
+            format!("{size}x{size}@{scale}x")
+        };
+        let apps_dir = theme_dir.join("hicolor").join(size_dir).join("apps");
+        if let Err(err) = std::fs::create_dir_all(&apps_dir) {
+            warn!("Failed to create fallback icon directory {:?}: {}", apps_dir, err);
+            return;
+        }
+
+        let icon_path = apps_dir.join(format!("{icon_name}.png"));
+        if icon_path.exists() {
+            return;
+        }
+
+        match Self::create_icon_surface_for_name(icon_name, size * scale) {
+            Some(mut surface) => {
+                if let Err(err) = Self::write_surface_png(&mut surface, &icon_path) {
+                    warn!("Failed to write fallback icon {:?}: {}", icon_path, err);
+                }
+            }
+            None => warn!("Failed to render fallback icon: {}", icon_name),
         }
     }
-    
-    /// Create a paintable for an icon
-    fn create_paintable_for_icon(icon_name: &str) -> Option<gdk::Paintable> {
-        // Create a surface using our icon renderer - but we need to draw the specific icon
-        if let Some(mut surface) = Self::create_icon_surface_for_name(icon_name, 16) {
-            // Convert to pixbuf
-            let width = surface.width();
-            let height = surface.height();
-            let stride = surface.stride();
-            
-            if let Ok(data) = surface.data() {
-                let pixbuf = gtk::gdk_pixbuf::Pixbuf::from_bytes(
-                    &glib::Bytes::from(&data[..]),
-                    gtk::gdk_pixbuf::Colorspace::Rgb,
-                    true, // has_alpha
-                    8,    // bits_per_sample
-                    width,
-                    height,
-                    stride,
-                );
-                
-                let texture = gdk::Texture::for_pixbuf(&pixbuf);
-                return Some(texture.upcast::<gdk::Paintable>());
+
+    /// Decide whether `icon_name` is genuinely missing from the active theme,
+    /// following the same chain GTK3's `GTK_ICON_LOOKUP_GENERIC_FALLBACK` used:
+    /// try the name itself, then its `-symbolic` variant, then progressively
+    /// more generic names obtained by dropping the trailing `-segment` after
+    /// the last dash (e.g. `audio-volume-high` -> `audio-volume` -> `audio`).
+    /// Only once no dash is left do we consider the icon missing, so themed
+    /// environments keep their real icons and we only draw genuine gaps.
+    fn is_genuinely_missing(icon_theme: &gtk::IconTheme, icon_name: &str) -> bool {
+        if icon_theme.has_icon(icon_name) {
+            return false;
+        }
+
+        let symbolic = format!("{icon_name}-symbolic");
+        if icon_theme.has_icon(&symbolic) {
+            return false;
+        }
+
+        let mut generic = icon_name.to_string();
+        while let Some(last_dash) = generic.rfind('-') {
+            generic.truncate(last_dash);
+            if icon_theme.has_icon(&generic) {
+                return false;
             }
         }
-        None
+
+        true
     }
-    
+
+    /// Write a cairo surface out as a PNG file.
+    fn write_surface_png(
+        surface: &mut gtk::cairo::ImageSurface,
+        path: &Path,
+    ) -> Result<(), std::io::Error> {
+        let mut file = std::fs::File::create(path)?;
+        surface
+            .write_to_png(&mut file)
+            .map_err(|err| std::io::Error::other(err.to_string()))
+    }
+
     /// Create a surface for a specific icon name
     fn create_icon_surface_for_name(icon_name: &str, size: i32) -> Option<gtk::cairo::ImageSurface> {
         use gtk::cairo;
-        
+
         // Create surface
         let surface = cairo::ImageSurface::create(cairo::Format::ARgb32, size, size).ok()?;
         let cr = cairo::Context::new(&surface).ok()?;
-        
+
         // Set up drawing context
         cr.set_source_rgba(0.0, 0.0, 0.0, 0.0); // Transparent background
         cr.paint().ok()?;
-        
+
         // Set drawing color (use theme-appropriate color)
         cr.set_source_rgba(0.2, 0.2, 0.2, 1.0); // Dark gray for visibility
         cr.set_line_width(1.0);
-        
+
         // Draw the appropriate icon
-        let success = match icon_name {
-            "io.bassi.Amberol" | "io.bassi.Amberol.Devel" => Self::draw_app_icon(&cr, size),
-            "web-browser-symbolic" | "user-home-symbolic" => Self::draw_web_browser(&cr, size),
-            "document-edit-symbolic" | "bug-symbolic" => Self::draw_bug(&cr, size),
-            "system-search-symbolic" => Self::draw_search(&cr, size),
-            "open-menu-symbolic" => Self::draw_menu(&cr, size),
-            "audio-only-symbolic" => Self::draw_audio(&cr, size),
-            "folder-music-symbolic" => Self::draw_folder(&cr, size),
-            "image-missing" => Self::draw_fallback(&cr, size),
-            _ => Self::draw_fallback(&cr, size),
-        };
-        
+        let success = Self::draw_app_icon(&cr, size);
+
         if success {
             Some(surface)
         } else {
             None
         }
     }
-    
+
     // Simple drawing functions for different icon types
     fn draw_app_icon(cr: &gtk::cairo::Context, size: i32) -> bool {
         let s = size as f64;
@@ -214,117 +357,52 @@ impl IconReplacer {
         cr.stroke().unwrap_or(());
         true
     }
-    
-    fn draw_web_browser(cr: &gtk::cairo::Context, size: i32) -> bool {
-        let s = size as f64;
-        // Globe
-        cr.arc(s * 0.5, s * 0.5, s * 0.35, 0.0, 2.0 * std::f64::consts::PI);
-        cr.stroke().unwrap_or(());
-        cr.move_to(s * 0.5, s * 0.15);
-        cr.line_to(s * 0.5, s * 0.85);
-        cr.stroke().unwrap_or(());
-        cr.move_to(s * 0.15, s * 0.5);
-        cr.line_to(s * 0.85, s * 0.5);
-        cr.stroke().unwrap_or(());
-        true
-    }
-    
-    fn draw_bug(cr: &gtk::cairo::Context, size: i32) -> bool {
-        let s = size as f64;
-        // Bug body
-        cr.arc(s * 0.5, s * 0.5, s * 0.25, 0.0, 2.0 * std::f64::consts::PI);
-        cr.stroke().unwrap_or(());
-        // Legs
-        for i in 0..3 {
-            let y = s * (0.3 + i as f64 * 0.2);
-            cr.move_to(s * 0.25, y);
-            cr.line_to(s * 0.1, y - s * 0.05);
-            cr.move_to(s * 0.75, y);
-            cr.line_to(s * 0.9, y - s * 0.05);
-            cr.stroke().unwrap_or(());
-        }
-        true
-    }
-    
-    fn draw_search(cr: &gtk::cairo::Context, size: i32) -> bool {
-        let s = size as f64;
-        // Magnifying glass
-        cr.arc(s * 0.4, s * 0.4, s * 0.2, 0.0, 2.0 * std::f64::consts::PI);
-        cr.stroke().unwrap_or(());
-        cr.move_to(s * 0.55, s * 0.55);
-        cr.line_to(s * 0.8, s * 0.8);
-        cr.stroke().unwrap_or(());
-        true
-    }
-    
-    fn draw_menu(cr: &gtk::cairo::Context, size: i32) -> bool {
-        let s = size as f64;
-        // Hamburger menu
-        for i in 0..3 {
-            let y = s * (0.3 + i as f64 * 0.2);
-            cr.move_to(s * 0.2, y);
-            cr.line_to(s * 0.8, y);
-            cr.stroke().unwrap_or(());
-        }
-        true
-    }
-    
-    fn draw_audio(cr: &gtk::cairo::Context, size: i32) -> bool {
-        Self::draw_app_icon(cr, size) // Same as app icon (musical note)
-    }
-    
-    fn draw_folder(cr: &gtk::cairo::Context, size: i32) -> bool {
-        let s = size as f64;
-        // Folder
-        cr.move_to(s * 0.1, s * 0.3);
-        cr.line_to(s * 0.1, s * 0.8);
-        cr.line_to(s * 0.9, s * 0.8);
-        cr.line_to(s * 0.9, s * 0.4);
-        cr.line_to(s * 0.6, s * 0.4);
-        cr.line_to(s * 0.5, s * 0.3);
-        cr.close_path();
-        cr.stroke().unwrap_or(());
-        // Music note inside
-        cr.arc(s * 0.4, s * 0.6, s * 0.05, 0.0, 2.0 * std::f64::consts::PI);
-        cr.fill().unwrap_or(());
-        true
-    }
-    
-    fn draw_fallback(cr: &gtk::cairo::Context, size: i32) -> bool {
-        let s = size as f64;
-        // Question mark
-        cr.arc(s * 0.5, s * 0.3, s * 0.1, 0.0, std::f64::consts::PI);
-        cr.stroke().unwrap_or(());
-        cr.arc(s * 0.5, s * 0.7, s * 0.05, 0.0, 2.0 * std::f64::consts::PI);
-        cr.fill().unwrap_or(());
-        true
-    }
-    
-    /// Force replacement of specific widgets by CSS class or ID
-    pub fn force_replace_known_widgets() {
-        info!("🎯 Force replacing known problematic widgets");
-        
-        // This is a more targeted approach for widgets we know are problematic
-        if let Some(app) = gtk::gio::Application::default() {
-            if let Some(gtk_app) = app.downcast_ref::<gtk::Application>() {
-                for window in gtk_app.windows() {
-                    // Look for about dialogs specifically
-                    if window.type_().name() == "AdwAboutWindow" {
-                        Self::fix_about_dialog(&window);
-                    }
-                }
-            }
-        }
-    }
-    
-    /// Fix icons in about dialog specifically
-    fn fix_about_dialog(window: &gtk::Window) {
-        info!("🔧 Fixing about dialog icons");
-        
-        // The about dialog's application icon is set via application_icon property
-        // We need to ensure our icon theme has the right icon
-        
-        // Find all images in the about dialog and replace them
-        Self::scan_widget_tree(window);
+
+    /// Build the scalable SVG content for a symbolic icon, recolorable by GTK
+    /// because every shape is filled/stroked with [`SYMBOLIC_FOREGROUND`].
+    fn symbolic_svg_for(icon_name: &str) -> String {
+        let fg = SYMBOLIC_FOREGROUND;
+        let body = match icon_name {
+            "web-browser-symbolic" | "user-home-symbolic" => format!(
+                r#"<circle cx="8" cy="8" r="5.5" fill="none" stroke="{fg}"/>
+<path d="M8 2.5 L8 13.5 M2.5 8 L13.5 8" stroke="{fg}"/>
+<path d="M4 4.5 Q8 6 8 8 Q8 10 4 11.5" fill="none" stroke="{fg}"/>"#
+            ),
+            "document-edit-symbolic" | "bug-symbolic" => format!(
+                r#"<ellipse cx="8" cy="8.5" rx="4" ry="4.5" fill="none" stroke="{fg}"/>
+<path d="M6.5 4 L5.5 2 M9.5 4 L10.5 2 M4 6 L2 5 M4 8.5 L2 8.5 M4 11 L2 12 M12 6 L14 5 M12 8.5 L14 8.5 M12 11 L14 12" stroke="{fg}"/>"#
+            ),
+            "system-search-symbolic" => format!(
+                r#"<circle cx="6.5" cy="6.5" r="3.5" fill="none" stroke="{fg}"/>
+<path d="M9 9 L13 13" stroke="{fg}" stroke-width="1.5"/>"#
+            ),
+            "open-menu-symbolic" => format!(
+                r#"<path d="M3 5 L13 5 M3 8 L13 8 M3 11 L13 11" stroke="{fg}" stroke-width="1.2"/>"#
+            ),
+            "audio-only-symbolic" => format!(
+                r#"<circle cx="5.5" cy="12" r="1.5" fill="{fg}"/>
+<circle cx="11.5" cy="10.5" r="1.5" fill="{fg}"/>
+<path d="M7 12 L7 5 L13 4 L13 10.5" fill="none" stroke="{fg}"/>"#
+            ),
+            "folder-music-symbolic" => format!(
+                r#"<path d="M2 3 L6 3 L8 5 L14 5 L14 13 L2 13 Z" fill="none" stroke="{fg}"/>
+<circle cx="6.5" cy="9.5" r="1" fill="{fg}"/>
+<path d="M7.5 9.5 L7.5 6.5 L10 6 L10 8.5" fill="none" stroke="{fg}"/>"#
+            ),
+            // "image-missing" and anything else we still draw for
+            _ => format!(
+                r#"<path d="M6 5 a2 2 0 1 1 3.5 1.3 Q8 7.5 8 9" fill="none" stroke="{fg}"/>
+<circle cx="8" cy="11.5" r="0.9" fill="{fg}"/>"#
+            ),
+        };
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="16" height="16" viewBox="0 0 16 16">
+<g fill-rule="evenodd" stroke-width="1" stroke-linecap="round">
+{body}
+</g>
+</svg>"#
+        )
     }
-}
\ No newline at end of file
+}
@@ -3,7 +3,67 @@
 
 use gtk::{gdk, glib, prelude::*};
 use log::{info, warn};
+use serde::Deserialize;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// An icon-set entry's color: either a fixed value, or `Default` to inherit
+/// the active theme's foreground.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum IconColor {
+    Default,
+    Custom(String),
+}
+
+/// A single icon's glyph (the SVG body between `<g>`/`</g>`) plus its color.
+#[derive(Debug, Clone, Deserialize)]
+struct IconSetEntry {
+    svg: String,
+    #[serde(default = "IconSetEntry::default_color")]
+    color: IconColor,
+}
+
+impl IconSetEntry {
+    fn default_color() -> IconColor {
+        IconColor::Default
+    }
+}
+
+/// A loadable flavor of Amberol's programmatic icon fallbacks: a name ->
+/// glyph/color mapping, so a user can swap in alternate icons without
+/// recompiling. See [`IconThemeProvider::load_icon_set`].
+#[derive(Debug, Clone, Default, Deserialize)]
+struct IconSet {
+    #[serde(default)]
+    icons: HashMap<String, IconSetEntry>,
+}
+
+/// A theme directory's size-matching rule, from its `index.theme` section,
+/// per the freedesktop Icon Theme Specification.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ThemeDirType {
+    Fixed,
+    Scalable,
+    Threshold,
+}
+
+/// One `Directories` entry from a theme's `index.theme`.
+#[derive(Debug, Clone)]
+struct ThemeSubdir {
+    path: String,
+    size: i32,
+    min_size: i32,
+    max_size: i32,
+    threshold: i32,
+    scale: i32,
+    kind: ThemeDirType,
+}
+
+/// Bump when the cached PNG format or the rendering that produces it
+/// changes, so a stale cache from an older Amberol build gets discarded
+/// instead of served back unchanged.
+const ICON_CACHE_FORMAT_VERSION: u32 = 1;
 
 /// Custom icon theme provider that intercepts icon lookups and provides programmatic alternatives
 pub struct IconThemeProvider;
@@ -29,31 +89,32 @@ impl IconThemeProvider {
     fn force_create_all_icons() {
         info!("🎯 Force creating all icons in multiple locations");
         
-        // Create icons in multiple theme directories for maximum coverage
+        // Create icons in multiple theme directories for maximum coverage.
+        // Each `NxN` directory gets icons generated at that actual pixel
+        // size instead of a fixed 16px SVG, so GTK's own size matching picks
+        // the right variant instead of upscaling a 16px icon to 48px.
         let icon_dirs = vec![
-            std::env::temp_dir().join("amberol-icons"),
-            std::env::temp_dir().join("hicolor").join("scalable").join("apps"),
-            std::env::temp_dir().join("hicolor").join("16x16").join("apps"),
-            std::env::temp_dir().join("hicolor").join("24x24").join("apps"),
-            std::env::temp_dir().join("hicolor").join("32x32").join("apps"),
-            std::env::temp_dir().join("hicolor").join("48x48").join("apps"),
+            (std::env::temp_dir().join("amberol-icons"), 16),
+            (std::env::temp_dir().join("hicolor").join("scalable").join("apps"), 16),
+            (std::env::temp_dir().join("hicolor").join("16x16").join("apps"), 16),
+            (std::env::temp_dir().join("hicolor").join("24x24").join("apps"), 24),
+            (std::env::temp_dir().join("hicolor").join("32x32").join("apps"), 32),
+            (std::env::temp_dir().join("hicolor").join("48x48").join("apps"), 48),
         ];
-        
-        for icon_dir in &icon_dirs {
+
+        for (icon_dir, size) in &icon_dirs {
             let _ = std::fs::create_dir_all(icon_dir);
-            Self::generate_missing_icons(icon_dir);
+            Self::generate_missing_icons_at_size(icon_dir, *size);
         }
-        
-        // Also add all these directories to the icon theme search path
+
+        // Also add all these directories to the icon theme search path.
+        // Re-adding a path GTK already knows about is a no-op, so there's
+        // no need to double up here: actual refreshes on theme/color-scheme
+        // changes are driven by the `notify` listeners set up in
+        // `Application::startup`, not by this call being re-triggered.
         if let Some(display) = gdk::Display::default() {
             let icon_theme = gtk::IconTheme::for_display(&display);
-            for icon_dir in &icon_dirs {
-                icon_theme.add_search_path(icon_dir);
-            }
-            
-            // Force the icon theme to refresh
-            // GTK doesn't provide a direct refresh method, but adding the same path twice can trigger it
-            for icon_dir in &icon_dirs {
+            for (icon_dir, _size) in &icon_dirs {
                 icon_theme.add_search_path(icon_dir);
             }
         }
@@ -65,11 +126,11 @@ impl IconThemeProvider {
             let icon_theme = gtk::IconTheme::for_display(&display);
             
             // Create textures for our programmatic icons and add them directly
-            let icon_replacements = Self::get_icon_replacement_map();
-            
-            for (icon_name, _replacement_data) in icon_replacements {
+            let icon_set = Self::load_icon_set();
+
+            for icon_name in icon_set.icons.keys() {
                 // Create a programmatic texture for this icon
-                if let Some(texture) = Self::create_icon_texture(&icon_name, 16) {
+                if Self::create_icon_texture(icon_name, 16).is_some() {
                     info!("🎨 Created direct replacement texture for: {}", icon_name);
                     // Unfortunately, GTK doesn't allow us to directly inject textures into the theme
                     // So we'll use the file-based approach but generate them immediately
@@ -78,55 +139,116 @@ impl IconThemeProvider {
         }
     }
     
-    /// Create a texture for a specific icon
+    /// `$XDG_CACHE_HOME/amberol/icons`, discarding whatever is already
+    /// there if its manifest is missing or names an older format version.
+    fn icon_cache_dir() -> Option<PathBuf> {
+        let cache_home = std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))?;
+
+        let cache_dir = cache_home.join("amberol").join("icons");
+        std::fs::create_dir_all(&cache_dir).ok()?;
+        Self::ensure_icon_cache_valid(&cache_dir);
+        Some(cache_dir)
+    }
+
+    /// Wipe `cache_dir` if its `manifest` file doesn't name the current
+    /// `ICON_CACHE_FORMAT_VERSION`, so bumping the version invalidates
+    /// everything rendered by older logic instead of serving it unchanged.
+    fn ensure_icon_cache_valid(cache_dir: &Path) {
+        let manifest_path = cache_dir.join("manifest");
+        let current_version = std::fs::read_to_string(&manifest_path)
+            .ok()
+            .and_then(|s| s.trim().parse::<u32>().ok());
+
+        if current_version == Some(ICON_CACHE_FORMAT_VERSION) {
+            return;
+        }
+
+        if let Ok(entries) = std::fs::read_dir(cache_dir) {
+            for entry in entries.flatten() {
+                let _ = std::fs::remove_file(entry.path());
+            }
+        }
+
+        let _ = std::fs::write(&manifest_path, ICON_CACHE_FORMAT_VERSION.to_string());
+    }
+
+    /// `{icon_name}-{size}-{scale}.png` under the icon cache directory.
+    fn cached_icon_path(cache_dir: &Path, icon_name: &str, size: i32, scale: i32) -> PathBuf {
+        cache_dir.join(format!("{icon_name}-{size}-{scale}.png"))
+    }
+
+    /// Create a texture for `icon_name` at 1x scale. The image is always
+    /// the Amberol app icon; `icon_name` only names the cache entry.
     fn create_icon_texture(icon_name: &str, size: i32) -> Option<gdk::Texture> {
-        // Use our existing icon renderer to create a surface
-        if let Some(mut surface) = crate::icon_renderer::IconRenderer::create_app_icon_surface(size) {
-            // Convert surface to pixbuf
-            let width = surface.width();
-            let height = surface.height();
-            let stride = surface.stride();
-            
-            if let Ok(data) = surface.data() {
-                let pixbuf = gtk::gdk_pixbuf::Pixbuf::from_bytes(
-                    &glib::Bytes::from(&data[..]),
-                    gtk::gdk_pixbuf::Colorspace::Rgb,
-                    true, // has_alpha
-                    8,    // bits_per_sample
-                    width,
-                    height,
-                    stride,
-                );
-                
-                return Some(gdk::Texture::for_pixbuf(&pixbuf));
+        Self::create_icon_texture_for_scale(icon_name, size, 1)
+    }
+
+    /// Like [`Self::create_icon_texture`], but renders at `size * scale`
+    /// pixels and persists the result as a PNG under the icon cache
+    /// directory, so later launches load the cached file instead of paying
+    /// for another Cairo render.
+    fn create_icon_texture_for_scale(icon_name: &str, size: i32, scale: i32) -> Option<gdk::Texture> {
+        let cache_dir = Self::icon_cache_dir();
+        let cached_path =
+            cache_dir.as_deref().map(|dir| Self::cached_icon_path(dir, icon_name, size, scale));
+
+        if let Some(path) = &cached_path {
+            if path.is_file() {
+                if let Ok(texture) = gdk::Texture::from_filename(path) {
+                    return Some(texture);
+                }
             }
         }
-        None
+
+        let mut surface = crate::icon_renderer::IconRenderer::create_app_icon_surface(size * scale)?;
+
+        if let Some(path) = &cached_path {
+            match std::fs::File::create(path) {
+                Ok(mut file) => {
+                    if let Err(e) = surface.write_to_png(&mut file) {
+                        warn!("⚠️ Failed to cache rendered icon {icon_name}: {e}");
+                    }
+                }
+                Err(e) => warn!("⚠️ Failed to create icon cache file for {icon_name}: {e}"),
+            }
+        }
+
+        Self::surface_to_texture(&mut surface)
     }
-    
-    /// Get mapping of icon names to replacement data
-    fn get_icon_replacement_map() -> HashMap<String, String> {
-        let mut map = HashMap::new();
-        
-        map.insert("io.bassi.Amberol".to_string(), "app".to_string());
-        map.insert("io.bassi.Amberol.Devel".to_string(), "app".to_string());
-        map.insert("web-browser-symbolic".to_string(), "web".to_string());
-        map.insert("user-home-symbolic".to_string(), "web".to_string());
-        map.insert("document-edit-symbolic".to_string(), "bug".to_string());
-        map.insert("bug-symbolic".to_string(), "bug".to_string());
-        map.insert("system-search-symbolic".to_string(), "search".to_string());
-        map.insert("open-menu-symbolic".to_string(), "menu".to_string());
-        map.insert("audio-only-symbolic".to_string(), "audio".to_string());
-        map.insert("folder-music-symbolic".to_string(), "folder".to_string());
-        
-        map
+
+    /// Convert a rendered Cairo surface into a `gdk::Texture`.
+    fn surface_to_texture(surface: &mut gtk::cairo::ImageSurface) -> Option<gdk::Texture> {
+        let width = surface.width();
+        let height = surface.height();
+        let stride = surface.stride();
+        let data = surface.data().ok()?;
+
+        let pixbuf = gtk::gdk_pixbuf::Pixbuf::from_bytes(
+            &glib::Bytes::from(&data[..]),
+            gtk::gdk_pixbuf::Colorspace::Rgb,
+            true, // has_alpha
+            8,    // bits_per_sample
+            width,
+            height,
+            stride,
+        );
+
+        Some(gdk::Texture::for_pixbuf(&pixbuf))
     }
-    
+
     /// Setup theme-based replacements as backup
     fn setup_theme_based_replacements() {
+        // Discover what the user actually picked, so the fallback icons we
+        // generate below match the icon theme already active on this
+        // desktop rather than defaulting to `hicolor` regardless.
+        let theme_name = Self::configured_icon_theme();
+        info!("🎨 Active icon theme for fallback generation: {theme_name}");
+
         if let Some(display) = gdk::Display::default() {
             let icon_theme = gtk::IconTheme::for_display(&display);
-            
+
             // Add our custom search path first (highest priority)
             if let Ok(temp_dir) = std::env::temp_dir().canonicalize() {
                 let custom_icons_dir = temp_dir.join("amberol-icons");
@@ -139,12 +261,9 @@ impl IconThemeProvider {
                 // Add to icon theme search path
                 icon_theme.add_search_path(&custom_icons_dir);
                 info!("📁 Added custom icon search path: {:?}", custom_icons_dir);
-                
+
                 // Generate programmatic icons on-demand
                 Self::generate_missing_icons(&custom_icons_dir);
-                
-                // Force icon theme to reload
-                icon_theme.add_search_path(&custom_icons_dir); // Add twice to trigger refresh
                        }
        }
    }
@@ -158,10 +277,10 @@ impl IconThemeProvider {
         let _ = std::fs::create_dir_all(&icon_dir);
         
         // Generate the specific icons needed for about dialog
-        Self::generate_icon_svg(&icon_dir, "io.bassi.Amberol");
-        Self::generate_icon_svg(&icon_dir, "io.bassi.Amberol.Devel");
-        Self::generate_icon_svg(&icon_dir, "web-browser-symbolic");
-        Self::generate_icon_svg(&icon_dir, "bug-symbolic");
+        Self::generate_icon_svg(&icon_dir, "io.bassi.Amberol", 16);
+        Self::generate_icon_svg(&icon_dir, "io.bassi.Amberol.Devel", 16);
+        Self::generate_icon_svg(&icon_dir, "web-browser-symbolic", 16);
+        Self::generate_icon_svg(&icon_dir, "bug-symbolic", 16);
         
         // Debug: Check if files were actually created
         for icon_name in &["io.bassi.Amberol", "io.bassi.Amberol.Devel", "web-browser-symbolic", "bug-symbolic"] {
@@ -190,31 +309,312 @@ impl IconThemeProvider {
         }
     }
    
-   /// Generate missing icons as SVG files in the custom icons directory
+   /// Generate missing icons as 16px SVG files in the custom icons directory.
     fn generate_missing_icons(icons_dir: &std::path::Path) {
+        Self::generate_missing_icons_at_size(icons_dir, 16);
+    }
+
+    /// Like [`Self::generate_missing_icons`], but renders every icon at
+    /// `size` pixels, so per-size theme directories (`16x16`, `48x48`, ...)
+    /// get a variant that actually matches their name instead of all
+    /// sharing the same fixed 16px SVG.
+    fn generate_missing_icons_at_size(icons_dir: &std::path::Path, size: i32) {
         let icons_to_generate = [
             "io.bassi.Amberol",
-            "io.bassi.Amberol.Devel", 
+            "io.bassi.Amberol.Devel",
             "web-browser-symbolic",
             "user-home-symbolic",
-            "document-edit-symbolic", 
+            "document-edit-symbolic",
             "bug-symbolic",
             "system-search-symbolic",
             "open-menu-symbolic",
             "audio-only-symbolic",
             "folder-music-symbolic",
         ];
-        
+
         for icon_name in &icons_to_generate {
-            Self::generate_icon_svg(icons_dir, icon_name);
+            Self::generate_icon_svg(icons_dir, icon_name, size);
         }
     }
     
+    /// Base directories to search for installed icon themes, per the
+    /// freedesktop Icon Theme Specification: `$XDG_DATA_DIRS/icons` and the
+    /// user's `~/.local/share/icons`. `/usr/share/pixmaps` is handled
+    /// separately, since it holds flat icon files rather than themes.
+    fn icon_theme_base_dirs() -> Vec<PathBuf> {
+        let mut dirs = Vec::new();
+
+        if let Some(home) = std::env::var_os("HOME") {
+            dirs.push(PathBuf::from(home).join(".local/share/icons"));
+        }
+
+        let xdg_data_dirs =
+            std::env::var("XDG_DATA_DIRS").unwrap_or_else(|_| "/usr/local/share:/usr/share".to_string());
+        for dir in xdg_data_dirs.split(':').filter(|d| !d.is_empty()) {
+            dirs.push(PathBuf::from(dir).join("icons"));
+        }
+
+        dirs
+    }
+
+    /// Parse a theme's `index.theme`, returning its `Directories` entries
+    /// (each with their own `Size`/`MinSize`/`MaxSize`/`Scale`/`Type`) and
+    /// its `Inherits` list. `None` if `theme_dir` has no `index.theme`.
+    fn parse_index_theme(theme_dir: &Path) -> Option<(Vec<ThemeSubdir>, Vec<String>)> {
+        let content = std::fs::read_to_string(theme_dir.join("index.theme")).ok()?;
+
+        let mut directories: Vec<String> = Vec::new();
+        let mut inherits: Vec<String> = Vec::new();
+        let mut sections: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let mut section = String::new();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(name) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                section = name.to_string();
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let (key, value) = (key.trim(), value.trim());
+
+            if section == "Icon Theme" {
+                match key {
+                    "Directories" => {
+                        directories = value.split(',').map(|s| s.trim().to_string()).collect();
+                    }
+                    "Inherits" => {
+                        inherits = value.split(',').map(|s| s.trim().to_string()).collect();
+                    }
+                    _ => {}
+                }
+            } else {
+                sections
+                    .entry(section.clone())
+                    .or_default()
+                    .insert(key.to_string(), value.to_string());
+            }
+        }
+
+        let subdirs = directories
+            .into_iter()
+            .filter_map(|path| {
+                let props = sections.get(&path)?;
+                let size = props.get("Size")?.parse().ok()?;
+                let kind = match props.get("Type").map(String::as_str) {
+                    Some("Fixed") => ThemeDirType::Fixed,
+                    Some("Threshold") => ThemeDirType::Threshold,
+                    _ => ThemeDirType::Scalable,
+                };
+                Some(ThemeSubdir {
+                    min_size: props.get("MinSize").and_then(|v| v.parse().ok()).unwrap_or(size),
+                    max_size: props.get("MaxSize").and_then(|v| v.parse().ok()).unwrap_or(size),
+                    threshold: props.get("Threshold").and_then(|v| v.parse().ok()).unwrap_or(2),
+                    scale: props.get("Scale").and_then(|v| v.parse().ok()).unwrap_or(1),
+                    path,
+                    size,
+                    kind,
+                })
+            })
+            .collect();
+
+        Some((subdirs, inherits))
+    }
+
+    /// Whether `dir` is an acceptable match for `requested`/`scale`, per the
+    /// Icon Theme Specification's `DirectoryMatchesSize`.
+    fn subdir_matches(dir: &ThemeSubdir, requested: i32, scale: i32) -> bool {
+        if dir.scale != scale {
+            return false;
+        }
+        match dir.kind {
+            ThemeDirType::Fixed => dir.size == requested,
+            ThemeDirType::Scalable => dir.min_size <= requested && requested <= dir.max_size,
+            ThemeDirType::Threshold => (dir.size - dir.threshold..=dir.size + dir.threshold).contains(&requested),
+        }
+    }
+
+    /// `DirectorySizeDistance`: how far `dir` is from `requested`/`scale`
+    /// when nothing matches exactly, so the closest size wins.
+    fn subdir_distance(dir: &ThemeSubdir, requested: i32, scale: i32) -> i32 {
+        (dir.size * dir.scale - requested * scale).abs()
+    }
+
+    /// `icon_name.{svg,png}` under `theme_dir/dir.path`, if it exists.
+    fn icon_file_in_subdir(theme_dir: &Path, dir: &ThemeSubdir, icon_name: &str) -> Option<PathBuf> {
+        ["svg", "png"].into_iter().find_map(|ext| {
+            let candidate = theme_dir.join(&dir.path).join(format!("{icon_name}.{ext}"));
+            candidate.is_file().then_some(candidate)
+        })
+    }
+
+    /// Pick the best subdir match for `icon_name`: an exact size/scale
+    /// match wins outright, otherwise the subdir minimizing
+    /// [`Self::subdir_distance`] among those that actually have the icon.
+    fn best_icon_in_subdirs(
+        theme_dir: &Path,
+        subdirs: &[ThemeSubdir],
+        icon_name: &str,
+        size: i32,
+        scale: i32,
+    ) -> Option<PathBuf> {
+        let mut best: Option<(PathBuf, i32)> = None;
+
+        for dir in subdirs {
+            let Some(path) = Self::icon_file_in_subdir(theme_dir, dir, icon_name) else {
+                continue;
+            };
+
+            if Self::subdir_matches(dir, size, scale) {
+                return Some(path);
+            }
+
+            let distance = Self::subdir_distance(dir, size, scale);
+            if best.as_ref().map_or(true, |(_, best_distance)| distance < *best_distance) {
+                best = Some((path, distance));
+            }
+        }
+
+        best.map(|(path, _)| path)
+    }
+
+    /// Search `theme_name` for `icon_name`, following its `Inherits` chain.
+    /// `visited` guards against inheritance cycles between themes.
+    fn find_icon_in_theme(
+        theme_name: &str,
+        icon_name: &str,
+        size: i32,
+        scale: i32,
+        visited: &mut std::collections::HashSet<String>,
+    ) -> Option<PathBuf> {
+        if !visited.insert(theme_name.to_string()) {
+            return None;
+        }
+
+        for base in Self::icon_theme_base_dirs() {
+            let theme_dir = base.join(theme_name);
+            let Some((subdirs, inherits)) = Self::parse_index_theme(&theme_dir) else {
+                continue;
+            };
+
+            if let Some(path) = Self::best_icon_in_subdirs(&theme_dir, &subdirs, icon_name, size, scale) {
+                return Some(path);
+            }
+
+            for parent in &inherits {
+                if let Some(path) = Self::find_icon_in_theme(parent, icon_name, size, scale, visited) {
+                    return Some(path);
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Resolve `icon_name` to a real file from an installed icon theme:
+    /// `theme_name`, then its `Inherits` chain, then `hicolor`, then the
+    /// flat `/usr/share/pixmaps` directory. `None` means no installed theme
+    /// provides this icon at all, in which case callers should fall back to
+    /// a generated SVG.
+    fn resolve_themed_icon(theme_name: &str, icon_name: &str, size: i32, scale: i32) -> Option<PathBuf> {
+        let mut visited = std::collections::HashSet::new();
+
+        if let Some(path) = Self::find_icon_in_theme(theme_name, icon_name, size, scale, &mut visited) {
+            return Some(path);
+        }
+
+        if theme_name != "hicolor" {
+            if let Some(path) = Self::find_icon_in_theme("hicolor", icon_name, size, scale, &mut visited) {
+                return Some(path);
+            }
+        }
+
+        ["png", "svg", "xpm"].into_iter().find_map(|ext| {
+            let candidate = Path::new("/usr/share/pixmaps").join(format!("{icon_name}.{ext}"));
+            candidate.is_file().then_some(candidate)
+        })
+    }
+
+    /// `$XDG_CONFIG_HOME`, or `~/.config` if it isn't set.
+    fn xdg_config_home() -> Option<PathBuf> {
+        if let Some(dir) = std::env::var_os("XDG_CONFIG_HOME") {
+            return Some(PathBuf::from(dir));
+        }
+        std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config"))
+    }
+
+    /// Read a single `key` out of `[section]` from a simple INI-style file,
+    /// shared by `kdeglobals` and GTK's `settings.ini`.
+    fn read_ini_value(path: &Path, section: &str, key: &str) -> Option<String> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let mut current = String::new();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if let Some(name) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                current = name.to_string();
+                continue;
+            }
+            if current != section {
+                continue;
+            }
+            if let Some((k, v)) = line.split_once('=') {
+                if k.trim() == key {
+                    return Some(v.trim().to_string());
+                }
+            }
+        }
+
+        None
+    }
+
+    /// The user's configured icon theme, read from the first of `kdeglobals`
+    /// (`[Icons] Theme`), GTK 4's `settings.ini` and GTK 3's `settings.ini`
+    /// (both `[Settings] gtk-icon-theme-name`) under `$XDG_CONFIG_HOME` that
+    /// sets one, defaulting to `hicolor` so Amberol looks native on KDE/GTK
+    /// setups with a custom theme instead of ignoring it.
+    fn configured_icon_theme() -> String {
+        let Some(config_home) = Self::xdg_config_home() else {
+            return "hicolor".to_string();
+        };
+
+        let candidates = [
+            (config_home.join("kdeglobals"), "Icons", "Theme"),
+            (config_home.join("gtk-4.0/settings.ini"), "Settings", "gtk-icon-theme-name"),
+            (config_home.join("gtk-3.0/settings.ini"), "Settings", "gtk-icon-theme-name"),
+        ];
+
+        for (path, section, key) in candidates {
+            if let Some(theme) = Self::read_ini_value(&path, section, key) {
+                info!("🎨 Using configured icon theme: {theme}");
+                return theme;
+            }
+        }
+
+        "hicolor".to_string()
+    }
+
     /// Generate a single icon as SVG file
-    fn generate_icon_svg(icons_dir: &std::path::Path, icon_name: &str) {
-        let svg_content = Self::create_svg_for_icon(icon_name);
+    fn generate_icon_svg(icons_dir: &std::path::Path, icon_name: &str, size: i32) {
+        if let Some(real_icon) = Self::resolve_themed_icon(&Self::configured_icon_theme(), icon_name, size, 1) {
+            info!("🎨 Found real themed icon for {icon_name} at {real_icon:?}, skipping programmatic fallback");
+            return;
+        }
+
+        let svg_content = Self::create_svg_for_icon(icon_name, size);
         let file_path = icons_dir.join(format!("{}.svg", icon_name));
-        
+
+        // `force_create_all_icons` regenerates every icon in six
+        // directories on each startup; skip the write entirely when the
+        // file already holds this exact content.
+        if std::fs::read_to_string(&file_path).ok().as_deref() == Some(svg_content.as_str()) {
+            return;
+        }
+
         match std::fs::write(&file_path, svg_content) {
             Ok(_) => {
                 info!("🎨 Generated programmatic icon: {} -> {:?}", icon_name, file_path);
@@ -225,35 +625,53 @@ impl IconThemeProvider {
         }
     }
     
-    /// Create SVG content for a specific icon
-    fn create_svg_for_icon(icon_name: &str) -> String {
-        let svg_header = r#"<?xml version="1.0" encoding="UTF-8"?>
-<svg xmlns="http://www.w3.org/2000/svg" width="16" height="16" viewBox="0 0 16 16">
-<g fill="currentColor" stroke="currentColor" stroke-width="1" fill-rule="evenodd">"#;
-        
-        let svg_footer = r#"</g>
-</svg>"#;
-        
-        let icon_content = match icon_name {
-            "io.bassi.Amberol" | "io.bassi.Amberol.Devel" => {
-                // Musical note
-                r#"<path d="M5 14 c-1.1 0 -2 -0.9 -2 -2 s0.9 -2 2 -2 s2 0.9 2 2 s-0.9 2 -2 2 z"/>
+    /// The glyph shown for an icon name that isn't in the loaded
+    /// [`IconSet`] — a bare question mark, same as the old `match`
+    /// fallback arm.
+    const UNKNOWN_ICON_SVG: &'static str =
+        r#"<text x="8" y="12" text-anchor="middle" font-size="12" font-family="monospace">?</text>"#;
+
+    /// The icon set Amberol ships with, before any user override is
+    /// merged in. Each entry's glyph is authored in a 16-unit design grid
+    /// (see [`Self::create_svg_for_icon`]) and colored [`IconColor::Default`],
+    /// i.e. the active theme's foreground.
+    fn default_icon_set() -> IconSet {
+        let mut icons = HashMap::new();
+
+        let mut insert = |name: &str, svg: &str| {
+            icons.insert(
+                name.to_string(),
+                IconSetEntry { svg: svg.to_string(), color: IconColor::Default },
+            );
+        };
+
+        // Musical note
+        insert(
+            "io.bassi.Amberol",
+            r#"<path d="M5 14 c-1.1 0 -2 -0.9 -2 -2 s0.9 -2 2 -2 s2 0.9 2 2 s-0.9 2 -2 2 z"/>
 <path d="M12 11 c-1.1 0 -2 -0.9 -2 -2 s0.9 -2 2 -2 s2 0.9 2 2 s-0.9 2 -2 2 z"/>
-<path d="M7 12 L7 4 L12 2 L12 9" stroke-width="1.5" fill="none"/>"#
-            }
-            "web-browser-symbolic" | "user-home-symbolic" => {
-                // Globe
-                r#"<circle cx="8" cy="8" r="6" fill="none" stroke-width="1"/>
+<path d="M7 12 L7 4 L12 2 L12 9" stroke-width="1.5" fill="none"/>"#,
+        );
+        insert(
+            "io.bassi.Amberol.Devel",
+            r#"<path d="M5 14 c-1.1 0 -2 -0.9 -2 -2 s0.9 -2 2 -2 s2 0.9 2 2 s-0.9 2 -2 2 z"/>
+<path d="M12 11 c-1.1 0 -2 -0.9 -2 -2 s0.9 -2 2 -2 s2 0.9 2 2 s-0.9 2 -2 2 z"/>
+<path d="M7 12 L7 4 L12 2 L12 9" stroke-width="1.5" fill="none"/>"#,
+        );
+
+        // Globe
+        let globe = r#"<circle cx="8" cy="8" r="6" fill="none" stroke-width="1"/>
 <path d="M8 2 L8 14" stroke-width="0.8"/>
 <path d="M2 8 L14 8" stroke-width="0.8"/>
 <path d="M5 3.5 Q8 5 8 8 Q8 11 11 12.5" fill="none" stroke-width="0.6"/>
 <path d="M11 3.5 Q8 5 8 8 Q8 11 5 12.5" fill="none" stroke-width="0.6"/>
 <path d="M3 5.5 Q6 6 10 6 Q13 5.5 13 5.5" fill="none" stroke-width="0.6"/>
-<path d="M3 10.5 Q6 10 10 10 Q13 10.5 13 10.5" fill="none" stroke-width="0.6"/>"#
-            }
-            "document-edit-symbolic" | "bug-symbolic" => {
-                // Bug
-                r#"<ellipse cx="8" cy="8.5" rx="4" ry="4.5"/>
+<path d="M3 10.5 Q6 10 10 10 Q13 10.5 13 10.5" fill="none" stroke-width="0.6"/>"#;
+        insert("web-browser-symbolic", globe);
+        insert("user-home-symbolic", globe);
+
+        // Bug
+        let bug = r#"<ellipse cx="8" cy="8.5" rx="4" ry="4.5"/>
 <line x1="6.5" y1="4" x2="5.5" y2="2" stroke-width="1"/>
 <line x1="9.5" y1="4" x2="10.5" y2="2" stroke-width="1"/>
 <line x1="4" y1="6" x2="2" y2="5" stroke-width="1"/>
@@ -261,52 +679,112 @@ impl IconThemeProvider {
 <line x1="4" y1="11" x2="2" y2="12" stroke-width="1"/>
 <line x1="12" y1="6" x2="14" y2="5" stroke-width="1"/>
 <line x1="12" y1="8.5" x2="14" y2="8.5" stroke-width="1"/>
-<line x1="12" y1="11" x2="14" y2="12" stroke-width="1"/>"#
-            }
-            "system-search-symbolic" => {
-                // Magnifying glass
-                r#"<circle cx="6" cy="6" r="4" fill="none" stroke-width="1.5"/>
-<line x1="9" y1="9" x2="13" y2="13" stroke-width="2"/>"#
-            }
-            "open-menu-symbolic" => {
-                // Hamburger menu
-                r#"<line x1="3" y1="5" x2="13" y2="5" stroke-width="1.5"/>
+<line x1="12" y1="11" x2="14" y2="12" stroke-width="1"/>"#;
+        insert("document-edit-symbolic", bug);
+        insert("bug-symbolic", bug);
+
+        // Magnifying glass
+        insert(
+            "system-search-symbolic",
+            r#"<circle cx="6" cy="6" r="4" fill="none" stroke-width="1.5"/>
+<line x1="9" y1="9" x2="13" y2="13" stroke-width="2"/>"#,
+        );
+
+        // Hamburger menu
+        insert(
+            "open-menu-symbolic",
+            r#"<line x1="3" y1="5" x2="13" y2="5" stroke-width="1.5"/>
 <line x1="3" y1="8" x2="13" y2="8" stroke-width="1.5"/>
-<line x1="3" y1="11" x2="13" y2="11" stroke-width="1.5"/>"#
-            }
-            "audio-only-symbolic" => {
-                // Music note
-                r#"<path d="M6 13 c-1 0 -1.5 -0.5 -1.5 -1.5 s0.5 -1.5 1.5 -1.5 s1.5 0.5 1.5 1.5 s-0.5 1.5 -1.5 1.5 z"/>
+<line x1="3" y1="11" x2="13" y2="11" stroke-width="1.5"/>"#,
+        );
+
+        // Music note
+        insert(
+            "audio-only-symbolic",
+            r#"<path d="M6 13 c-1 0 -1.5 -0.5 -1.5 -1.5 s0.5 -1.5 1.5 -1.5 s1.5 0.5 1.5 1.5 s-0.5 1.5 -1.5 1.5 z"/>
 <path d="M7.5 11.5 L7.5 5 L11 4 L11 8.5" stroke-width="1.2" fill="none"/>
-<path d="M11 10 c-0.8 0 -1.2 -0.4 -1.2 -1.2 s0.4 -1.2 1.2 -1.2 s1.2 0.4 1.2 1.2 s-0.4 1.2 -1.2 1.2 z"/>"#
-            }
-            "folder-music-symbolic" => {
-                // Folder with music note
-                r#"<path d="M2 3 L2 13 L14 13 L14 5 L8 5 L6 3 Z" fill="none" stroke-width="1"/>
+<path d="M11 10 c-0.8 0 -1.2 -0.4 -1.2 -1.2 s0.4 -1.2 1.2 -1.2 s1.2 0.4 1.2 1.2 s-0.4 1.2 -1.2 1.2 z"/>"#,
+        );
+
+        // Folder with music note
+        insert(
+            "folder-music-symbolic",
+            r#"<path d="M2 3 L2 13 L14 13 L14 5 L8 5 L6 3 Z" fill="none" stroke-width="1"/>
 <path d="M6 10 c-0.5 0 -1 -0.5 -1 -1 s0.5 -1 1 -1 s1 0.5 1 1 s-0.5 1 -1 1 z"/>
-<path d="M7 9 L7 6.5 L9.5 6 L9.5 8" stroke-width="0.8" fill="none"/>"#
-            }
-            _ => {
-                // Default fallback
-                r#"<text x="8" y="12" text-anchor="middle" font-size="12" font-family="monospace">?</text>"#
+<path d="M7 9 L7 6.5 L9.5 6 L9.5 8" stroke-width="0.8" fill="none"/>"#,
+        );
+
+        IconSet { icons }
+    }
+
+    /// The icon set actually in effect: [`Self::default_icon_set`], with
+    /// any entries from `$XDG_CONFIG_HOME/amberol/icons.json` overlaid on
+    /// top, so a user can recolor or redraw individual icons without
+    /// recompiling Amberol.
+    fn load_icon_set() -> IconSet {
+        let mut icon_set = Self::default_icon_set();
+
+        let Some(config_home) = Self::xdg_config_home() else {
+            return icon_set;
+        };
+        let override_path = config_home.join("amberol").join("icons.json");
+
+        match std::fs::read_to_string(&override_path) {
+            Ok(contents) => match serde_json::from_str::<IconSet>(&contents) {
+                Ok(overrides) => {
+                    info!(
+                        "🎨 Loaded {} icon override(s) from {:?}",
+                        overrides.icons.len(),
+                        override_path
+                    );
+                    icon_set.icons.extend(overrides.icons);
+                }
+                Err(e) => warn!("⚠️ Failed to parse icon set {override_path:?}: {e}"),
+            },
+            Err(_) => {
+                // No override file is the common case; nothing to log.
             }
+        }
+
+        icon_set
+    }
+
+    /// Resolve an [`IconColor`] to the hex string `create_svg_for_icon`
+    /// substitutes into the SVG's `fill`/`stroke` attributes.
+    fn resolve_icon_color(color: &IconColor) -> String {
+        match color {
+            IconColor::Custom(hex) => hex.clone(),
+            // `#2e3436` is the same magic foreground GTK's symbolic-SVG
+            // recoloring convention uses (see `SYMBOLIC_FOREGROUND` in
+            // `icon_replacer.rs`), so the default icon set blends in with
+            // real symbolic theme icons instead of standing out.
+            IconColor::Default => "#2e3436".to_string(),
+        }
+    }
+
+    /// Create SVG content for a specific icon at `size` pixels. The glyph
+    /// paths are all authored in a 16-unit design grid, so `viewBox` stays
+    /// `0 0 16 16` and only `width`/`height` track `size` — that's what
+    /// actually makes the icon crisp at a given size instead of upscaling
+    /// a fixed-size raster, and it's why a `48x48` theme directory and a
+    /// `16x16` one can both hold a variant of this same SVG.
+    fn create_svg_for_icon(icon_name: &str, size: i32) -> String {
+        let icon_set = Self::load_icon_set();
+        let (icon_content, color) = match icon_set.icons.get(icon_name) {
+            Some(entry) => (entry.svg.clone(), Self::resolve_icon_color(&entry.color)),
+            None => (Self::UNKNOWN_ICON_SVG.to_string(), Self::resolve_icon_color(&IconColor::Default)),
         };
-        
+
+        let svg_header = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 16 16">
+<g fill="{color}" stroke="{color}" stroke-width="1" fill-rule="evenodd">"#
+        );
+
+        let svg_footer = r#"</g>
+</svg>"#;
+
         format!("{}{}{}", svg_header, icon_content, svg_footer)
     }
     
-    /// Ensure programmatic icons are available when theme changes
-    fn ensure_programmatic_icons_available(icon_theme: &gtk::IconTheme) {
-        if let Ok(temp_dir) = std::env::temp_dir().canonicalize() {
-            let custom_icons_dir = temp_dir.join("amberol-icons");
-            
-            if !custom_icons_dir.exists() {
-                let _ = std::fs::create_dir_all(&custom_icons_dir);
-                Self::generate_missing_icons(&custom_icons_dir);
-                
-                // Add to search path if not already added
-                icon_theme.add_search_path(&custom_icons_dir);
-            }
-        }
-    }
 }
\ No newline at end of file
@@ -0,0 +1,90 @@
+// SPDX-FileCopyrightText: 2024  Emmanuele Bassi
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Sleep inhibition on Linux/FreeBSD, mirroring what
+//! [`crate::windows::WindowsPowerManager`] does with
+//! `SetThreadExecutionState` on Windows.
+//!
+//! This goes through `GtkApplication::inhibit()`, which asks the session's
+//! idle/power manager (through logind on most distributions) not to suspend
+//! while music is playing, without us having to talk to D-Bus directly.
+
+use std::cell::Cell;
+
+use gtk::prelude::*;
+use log::debug;
+
+use crate::audio::{Controller, PlaybackState, RepeatMode, Song};
+
+/// Holds the cookie for an active inhibitor; dropping it (or calling
+/// [`Self::allow_sleep`]) releases the inhibit request.
+pub struct LinuxPowerManager {
+    app: gtk::Application,
+    cookie: Cell<Option<u32>>,
+}
+
+impl LinuxPowerManager {
+    pub fn new(app: &gtk::Application) -> Self {
+        Self {
+            app: app.clone(),
+            cookie: Cell::new(None),
+        }
+    }
+
+    /// Prevent system suspend while music is playing.
+    pub fn prevent_sleep(&self) {
+        if self.cookie.get().is_some() {
+            return;
+        }
+
+        let window = self.app.active_window();
+        let cookie = self.app.inhibit(
+            window.as_ref(),
+            gtk::ApplicationInhibitFlags::SUSPEND,
+            Some("Playing music"),
+        );
+
+        debug!("Linux: Preventing system sleep for music playback (cookie {cookie})");
+        self.cookie.set(Some(cookie));
+    }
+
+    /// Allow system suspend again when playback stops or pauses.
+    pub fn allow_sleep(&self) {
+        if let Some(cookie) = self.cookie.take() {
+            debug!("Linux: Allowing system sleep (cookie {cookie})");
+            self.app.uninhibit(cookie);
+        }
+    }
+}
+
+impl Drop for LinuxPowerManager {
+    fn drop(&mut self) {
+        self.allow_sleep();
+    }
+}
+
+impl std::fmt::Debug for LinuxPowerManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LinuxPowerManager")
+            .field("cookie", &self.cookie.get())
+            .finish()
+    }
+}
+
+/// Register this alongside `MprisController` in `AudioPlayer`'s controller
+/// list so sleep is inhibited only while a track is actually playing, not
+/// for the whole lifetime of the app.
+impl Controller for LinuxPowerManager {
+    fn set_playback_state(&self, state: &PlaybackState) {
+        match state {
+            PlaybackState::Playing => self.prevent_sleep(),
+            _ => self.allow_sleep(),
+        }
+    }
+
+    fn set_song(&self, _song: &Song) {}
+
+    fn set_position(&self, _position: u64) {}
+
+    fn set_repeat_mode(&self, _repeat: RepeatMode) {}
+}
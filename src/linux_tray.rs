@@ -0,0 +1,450 @@
+// SPDX-FileCopyrightText: 2024  Emmanuele Bassi
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! A freedesktop StatusNotifierItem tray for Linux/FreeBSD, backing
+//! `system_tray::SystemTray` on this platform so Amberol gets the same
+//! background media controls the Windows tray already provides.
+
+use gtk::{gio, glib, prelude::*};
+use log::{info, warn};
+use zbus::{dbus_interface, dbus_proxy, zvariant, Connection};
+
+const SNI_PATH: &str = "/StatusNotifierItem";
+const MENU_PATH: &str = "/com/canonical/dbusmenu";
+
+/// What a middle-click (`SecondaryActivate`) on the tray does. Configurable
+/// independently of left-click/`Activate` (always "raise the window"),
+/// since which gesture a host maps to which click varies. Defaults to
+/// [`Self::ToggleMute`]: mute is the one action with no other path from
+/// the tray, since play/pause and next are in the context menu and the
+/// scroll wheel already covers volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MiddleClickAction {
+    PlayPause,
+    ToggleMute,
+    Next,
+}
+
+impl MiddleClickAction {
+    /// Parse the `tray-middle-click-action` GSettings string, falling back
+    /// to the default for anything unrecognized (including an unset key).
+    fn from_settings_str(value: &str) -> Self {
+        match value {
+            "play-pause" => Self::PlayPause,
+            "next" => Self::Next,
+            _ => Self::ToggleMute,
+        }
+    }
+}
+
+/// The `org.kde.StatusNotifierItem` object we register on the session bus.
+struct StatusNotifierItem {
+    title: String,
+    status: String,
+    /// Raw ARGB32 pixmaps at a handful of sizes, rendered through the same
+    /// `IconRenderer` surfaces used elsewhere, so the tray shows up correctly
+    /// even on hosts whose icon theme has no `io.bassi.Amberol` icon.
+    icon_pixmap: Vec<(i32, i32, Vec<u8>)>,
+    /// The `ToolTip` description, updated to the current playback state and
+    /// track by [`LinuxTray::update_tooltip`]; empty until something plays.
+    tooltip: String,
+}
+
+#[dbus_interface(name = "org.kde.StatusNotifierItem")]
+impl StatusNotifierItem {
+    #[dbus_interface(property)]
+    fn category(&self) -> &str {
+        "Multimedia"
+    }
+
+    #[dbus_interface(property)]
+    fn id(&self) -> &str {
+        "io.bassi.Amberol"
+    }
+
+    #[dbus_interface(property)]
+    fn title(&self) -> &str {
+        &self.title
+    }
+
+    #[dbus_interface(property)]
+    fn status(&self) -> &str {
+        &self.status
+    }
+
+    #[dbus_interface(property)]
+    fn icon_name(&self) -> &str {
+        "io.bassi.Amberol"
+    }
+
+    #[dbus_interface(property)]
+    fn icon_pixmap(&self) -> Vec<(i32, i32, Vec<u8>)> {
+        self.icon_pixmap.clone()
+    }
+
+    #[dbus_interface(property)]
+    fn tool_tip(&self) -> (String, Vec<(i32, i32, Vec<u8>)>, String, String) {
+        ("io.bassi.Amberol".to_string(), self.icon_pixmap.clone(), self.title.clone(), self.tooltip.clone())
+    }
+
+    fn activate(&self, _x: i32, _y: i32) {
+        info!("🎵 Tray activated, raising main window");
+        glib::idle_add_once(|| {
+            if let Some(app) = gio::Application::default() {
+                app.activate();
+            }
+        });
+    }
+
+    /// Middle-click (or whatever the host maps to it) runs the action
+    /// configured via `tray-middle-click-action` instead of raising the
+    /// window, so there's a one-click control that doesn't require opening
+    /// the context menu.
+    fn secondary_activate(&self, _x: i32, _y: i32) {
+        glib::idle_add_once(|| {
+            if let Some(app) = gio::Application::default() {
+                if let Some(app) = app.downcast_ref::<crate::application::Application>() {
+                    let action = MiddleClickAction::from_settings_str(
+                        &app.settings().string("tray-middle-click-action"),
+                    );
+                    info!("🎵 Tray secondary-activated, running {action:?}");
+                    match action {
+                        MiddleClickAction::PlayPause => app.player().toggle(),
+                        MiddleClickAction::ToggleMute => app.player().toggle_mute(),
+                        MiddleClickAction::Next => app.player().skip(),
+                    }
+                }
+            }
+        });
+    }
+
+    /// Hosts that don't understand `com.canonical.dbusmenu` call this
+    /// instead of walking the `Menu` property; the best we can do without a
+    /// native popup of our own is raise the main window, same as `Activate`.
+    fn context_menu(&self, _x: i32, _y: i32) {
+        info!("🎵 Tray context menu requested, raising main window");
+        glib::idle_add_once(|| {
+            if let Some(app) = gio::Application::default() {
+                app.activate();
+            }
+        });
+    }
+
+    /// Mouse wheel over the tray icon nudges the volume, the same gesture
+    /// most status bar hosts already wire up for volume applets. The step
+    /// is clamped to the 0.0-1.0 range `AudioPlayer` uses internally, and
+    /// the resulting level is mirrored onto the tray's volume-meter overlay
+    /// so it doesn't lag behind scroll input from outside the app.
+    fn scroll(&self, delta: i32, orientation: &str) {
+        if orientation != "vertical" || delta == 0 {
+            return;
+        }
+
+        let step = if delta > 0 { 0.05 } else { -0.05 };
+        glib::idle_add_once(move || {
+            if let Some(app) = gio::Application::default() {
+                if let Some(app) = app.downcast_ref::<crate::application::Application>() {
+                    let player = app.player();
+                    let new_volume = (player.volume() + step).clamp(0.0, 1.0);
+                    player.set_volume(new_volume);
+                    app.set_tray_volume(new_volume);
+                }
+            }
+        });
+    }
+
+    /// Legacy counterpart to the `IconPixmap`/`org.freedesktop.DBus.Properties`
+    /// change notification, for hosts that only listen for this signal
+    /// instead of property-changed.
+    #[dbus_interface(signal)]
+    async fn new_icon(ctx: &zbus::SignalContext<'_>) -> zbus::Result<()>;
+
+    /// Legacy counterpart to the `Status` property-changed notification.
+    #[dbus_interface(signal)]
+    async fn new_status(ctx: &zbus::SignalContext<'_>) -> zbus::Result<()>;
+}
+
+/// The `com.canonical.dbusmenu` object backing the context menu.
+struct DBusMenu;
+
+const MENU_ID_PLAY_PAUSE: i32 = 1;
+const MENU_ID_PREVIOUS: i32 = 2;
+const MENU_ID_NEXT: i32 = 3;
+const MENU_ID_QUIT: i32 = 4;
+const MENU_ID_SHOW: i32 = 5;
+const MENU_ID_SEPARATOR: i32 = 6;
+
+#[dbus_interface(name = "com.canonical.dbusmenu")]
+impl DBusMenu {
+    fn get_layout(
+        &self,
+        _parent_id: i32,
+        _recursion_depth: i32,
+        _property_names: Vec<String>,
+    ) -> (u32, (i32, std::collections::HashMap<String, zvariant::OwnedValue>, Vec<zvariant::OwnedValue>)) {
+        let children = vec![
+            Self::label_item(MENU_ID_PLAY_PAUSE, "Play/Pause"),
+            Self::label_item(MENU_ID_PREVIOUS, "Previous"),
+            Self::label_item(MENU_ID_NEXT, "Next"),
+            Self::separator_item(MENU_ID_SEPARATOR),
+            Self::label_item(MENU_ID_SHOW, "Show Amberol"),
+            Self::label_item(MENU_ID_QUIT, "Quit"),
+        ];
+
+        (0, (0, std::collections::HashMap::new(), children))
+    }
+
+    fn event(&self, id: i32, event_id: &str, _data: zvariant::Value<'_>, _timestamp: u32) {
+        info!("🎵 Tray menu event {event_id} on item {id}");
+        let action = match id {
+            MENU_ID_PLAY_PAUSE => Some("play-pause"),
+            MENU_ID_PREVIOUS => Some("previous"),
+            MENU_ID_NEXT => Some("next"),
+            MENU_ID_QUIT => Some("quit"),
+            MENU_ID_SHOW => Some("show"),
+            _ => None,
+        };
+
+        if let Some(action) = action {
+            glib::idle_add_once(move || {
+                let Some(app) = gio::Application::default() else {
+                    return;
+                };
+
+                match action {
+                    "quit" => app.quit(),
+                    "show" => app.activate(),
+                    _ => {
+                        if let Some(app) = app.downcast_ref::<gtk::Application>() {
+                            if let Some(action_obj) = app.lookup_action(action) {
+                                action_obj.activate(None);
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    }
+}
+
+impl DBusMenu {
+    /// Build a single `com.canonical.dbusmenu` layout entry for a clickable,
+    /// labelled item with no children.
+    fn label_item(id: i32, label: &str) -> zvariant::OwnedValue {
+        let mut properties: std::collections::HashMap<String, zvariant::Value> =
+            std::collections::HashMap::new();
+        properties.insert("label".to_string(), zvariant::Value::from(label));
+        properties.insert("enabled".to_string(), zvariant::Value::from(true));
+
+        Self::build_item(id, properties)
+    }
+
+    /// Build a `com.canonical.dbusmenu` separator entry.
+    fn separator_item(id: i32) -> zvariant::OwnedValue {
+        let mut properties: std::collections::HashMap<String, zvariant::Value> =
+            std::collections::HashMap::new();
+        properties.insert("type".to_string(), zvariant::Value::from("separator"));
+
+        Self::build_item(id, properties)
+    }
+
+    fn build_item(
+        id: i32,
+        properties: std::collections::HashMap<String, zvariant::Value>,
+    ) -> zvariant::OwnedValue {
+        let structure = zvariant::StructureBuilder::new()
+            .add_field(id)
+            .add_field(properties)
+            .add_field(Vec::<zvariant::Value>::new())
+            .build()
+            .expect("well-formed dbusmenu layout entry");
+
+        zvariant::Value::Structure(structure)
+            .try_into()
+            .expect("dbusmenu layout entry converts to an owned value")
+    }
+}
+
+/// Handle to the running tray; dropping it does not unregister the D-Bus
+/// names (the connection is kept alive for the app's lifetime instead).
+pub struct LinuxTray {
+    connection: Connection,
+}
+
+#[dbus_proxy(
+    interface = "org.kde.StatusNotifierWatcher",
+    default_service = "org.kde.StatusNotifierWatcher",
+    default_path = "/StatusNotifierWatcher"
+)]
+trait StatusNotifierWatcher {
+    fn register_status_notifier_item(&self, service: &str) -> zbus::Result<()>;
+}
+
+impl LinuxTray {
+    /// Render the app icon at a few common tray sizes into the `(width,
+    /// height, ARGB32 bytes)` tuples the `icon_pixmap` property expects, per
+    /// the StatusNotifierItem spec.
+    fn render_icon_pixmap() -> Vec<(i32, i32, Vec<u8>)> {
+        [22, 32, 48]
+            .into_iter()
+            .filter_map(|size| {
+                let surface = crate::icon_renderer::IconRenderer::create_app_icon_surface(size)?;
+                Self::surface_to_pixmap(size, surface)
+            })
+            .collect()
+    }
+
+    /// Pack a Cairo ARGB32 surface into the `(width, height, bytes)` tuple
+    /// the `icon_pixmap` property expects, shared by the static app icon and
+    /// [`Self::update_icon_pixmap`]'s live playback overlay alike.
+    pub(crate) fn surface_to_pixmap(
+        size: i32,
+        mut surface: gtk::cairo::ImageSurface,
+    ) -> Option<(i32, i32, Vec<u8>)> {
+        let stride = surface.stride();
+        let data = surface.data().ok()?;
+
+        let mut pixels = Vec::with_capacity((size * size * 4) as usize);
+        for y in 0..size {
+            let row_start = (y * stride) as usize;
+            let row = &data[row_start..row_start + (size * 4) as usize];
+            for pixel in row.chunks_exact(4) {
+                // cairo's ARGB32 is premultiplied and native-endian,
+                // i.e. stored as B, G, R, A on little-endian hosts;
+                // icon_pixmap wants network-order (big-endian) ARGB.
+                let (b, g, r, a) = (pixel[0], pixel[1], pixel[2], pixel[3]);
+                pixels.extend_from_slice(&[a, r, g, b]);
+            }
+        }
+
+        Some((size, size, pixels))
+    }
+
+    /// Register the tray item and menu on the session bus, and announce it
+    /// to the `StatusNotifierWatcher`. Returns `None` (with a warning) if no
+    /// watcher is present, which is common on desktops without a tray.
+    pub fn new() -> Option<Self> {
+        let connection = glib::MainContext::default()
+            .block_on(async { Connection::session().await })
+            .map_err(|e| warn!("Unable to connect to session bus for tray: {e}"))
+            .ok()?;
+
+        let well_known_name = format!("org.kde.StatusNotifierItem-{}-1", std::process::id());
+
+        glib::MainContext::default().block_on(async {
+            if let Err(e) = connection
+                .object_server()
+                .at(
+                    SNI_PATH,
+                    StatusNotifierItem {
+                        title: "Amberol".to_string(),
+                        status: "Active".to_string(),
+                        icon_pixmap: Self::render_icon_pixmap(),
+                        tooltip: String::new(),
+                    },
+                )
+                .await
+            {
+                warn!("Failed to export StatusNotifierItem: {e}");
+            }
+
+            if let Err(e) = connection.object_server().at(MENU_PATH, DBusMenu).await {
+                warn!("Failed to export dbusmenu: {e}");
+            }
+
+            if let Err(e) = connection.request_name(well_known_name.as_str()).await {
+                warn!("Failed to acquire tray bus name: {e}");
+                return;
+            }
+
+            match StatusNotifierWatcherProxy::new(&connection).await {
+                Ok(watcher) => {
+                    if let Err(e) = watcher
+                        .register_status_notifier_item(&well_known_name)
+                        .await
+                    {
+                        warn!("No StatusNotifierWatcher available, tray will be inactive: {e}");
+                    } else {
+                        info!("✅ Registered Amberol StatusNotifierItem tray");
+                    }
+                }
+                Err(e) => warn!("Could not reach StatusNotifierWatcher: {e}"),
+            }
+        });
+
+        Some(Self { connection })
+    }
+
+    /// Push a freshly-rendered icon to the already-registered
+    /// `StatusNotifierItem` and tell whoever is watching (typically the
+    /// status bar host) that `icon_pixmap` changed, so a
+    /// [`crate::playback_icon_renderer::PlaybackIconRenderer`] update shows
+    /// up without waiting for the host to poll.
+    pub fn update_icon_pixmap(&self, pixmap: Vec<(i32, i32, Vec<u8>)>) {
+        let connection = self.connection.clone();
+        glib::MainContext::default().block_on(async move {
+            let iface_ref = match connection
+                .object_server()
+                .interface::<_, StatusNotifierItem>(SNI_PATH)
+                .await
+            {
+                Ok(iface_ref) => iface_ref,
+                Err(e) => {
+                    warn!("Tray item not registered, dropping icon update: {e}");
+                    return;
+                }
+            };
+
+            iface_ref.get_mut().await.icon_pixmap = pixmap;
+
+            let ctx = iface_ref.signal_context();
+            if let Err(e) = StatusNotifierItem::icon_pixmap_changed(ctx).await {
+                warn!("Failed to announce tray icon update: {e}");
+            }
+            if let Err(e) = StatusNotifierItem::new_icon(ctx).await {
+                warn!("Failed to announce tray icon update (legacy signal): {e}");
+            }
+        });
+    }
+
+    /// Push a new `ToolTip` description (the playback state/track line, or
+    /// empty before anything has played) and `Status` (`"Active"` while
+    /// playing, `"Passive"` otherwise), and announce both changes, mirroring
+    /// [`Self::update_icon_pixmap`].
+    pub fn update_tooltip(&self, playing: bool, description: String) {
+        let connection = self.connection.clone();
+        glib::MainContext::default().block_on(async move {
+            let iface_ref = match connection
+                .object_server()
+                .interface::<_, StatusNotifierItem>(SNI_PATH)
+                .await
+            {
+                Ok(iface_ref) => iface_ref,
+                Err(e) => {
+                    warn!("Tray item not registered, dropping tooltip update: {e}");
+                    return;
+                }
+            };
+
+            let status = if playing { "Active" } else { "Passive" };
+            let mut item = iface_ref.get_mut().await;
+            let status_changed = item.status != status;
+            item.tooltip = description;
+            item.status = status.to_string();
+            drop(item);
+
+            let ctx = iface_ref.signal_context();
+            if let Err(e) = StatusNotifierItem::tool_tip_changed(ctx).await {
+                warn!("Failed to announce tray tooltip update: {e}");
+            }
+            if status_changed {
+                if let Err(e) = StatusNotifierItem::status_changed(ctx).await {
+                    warn!("Failed to announce tray status update: {e}");
+                }
+                if let Err(e) = StatusNotifierItem::new_status(ctx).await {
+                    warn!("Failed to announce tray status update (legacy signal): {e}");
+                }
+            }
+        });
+    }
+}
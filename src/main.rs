@@ -30,8 +30,15 @@ mod waveform_view;
 mod window;
 #[cfg(target_os = "windows")]
 mod windows;
+#[cfg(not(target_os = "windows"))]
+mod linux_tray;
+#[cfg(not(target_os = "windows"))]
+mod linux_power;
 mod system_tray;
 mod icon_renderer;
+mod playback_icon_renderer;
+mod replaygain_controller;
+mod session_controller;
 
 use std::env;
 
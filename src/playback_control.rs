@@ -72,6 +72,11 @@ mod imp {
             self.parent_constructed();
 
             self.menu_button.set_primary(true);
+
+            // Make sure the repeat/shuffle/volume glyphs reflect the
+            // current icon theme and color scheme as soon as this
+            // instance's buttons exist, not just after the next change.
+            IconRenderer::refresh_all();
         }
     }
 
@@ -0,0 +1,220 @@
+// SPDX-FileCopyrightText: 2024  Emmanuele Bassi
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Composites live playback state onto the tray/taskbar icon, the way
+//! pnmixer overlays a volume meter onto its base pixbuf.
+//!
+//! [`PlaybackIconRenderer`] is a [`Controller`] like `MprisController` or
+//! `LinuxPowerManager`: `AudioPlayer` drives it with state, position and
+//! song updates, and it turns those into a small glyph and progress arc
+//! drawn over the base app icon from [`crate::icon_renderer::IconRenderer`],
+//! pushed to the platform [`crate::system_tray::SystemTray`] through
+//! [`crate::application::Application::update_tray_playback_icon`].
+
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+use gtk::{cairo, glib, prelude::*};
+use log::debug;
+
+use crate::{
+    application::Application,
+    audio::{Controller, PlaybackState, RepeatMode, Song},
+    icon_renderer::IconRenderer,
+};
+
+/// Minimum spacing between icon redraws; `AudioPlayer` reports position at
+/// sub-second granularity, which is far more often than a tray icon needs
+/// to repaint.
+const MIN_UPDATE_INTERVAL: Duration = Duration::from_millis(300);
+
+/// Size of the state/progress overlay, relative to the icon's side. The
+/// result is not clamped at small tray sizes.
+const OVERLAY_SCALE: f64 = 0.4;
+
+pub struct PlaybackIconRenderer {
+    app: Application,
+    state: RefCell<PlaybackState>,
+    position: Cell<u64>,
+    duration: Cell<u64>,
+    last_update: Cell<Option<Instant>>,
+    /// `(title, artist)` of the current song, kept around so a playback
+    /// state change alone (no new `set_song` call) can still refresh the
+    /// tray tooltip with the right track.
+    track: RefCell<(String, String)>,
+}
+
+impl PlaybackIconRenderer {
+    pub fn new(app: &Application) -> Self {
+        Self {
+            app: app.clone(),
+            state: RefCell::new(PlaybackState::Stopped),
+            position: Cell::new(0),
+            duration: Cell::new(0),
+            last_update: Cell::new(None),
+            track: RefCell::new((String::new(), String::new())),
+        }
+    }
+
+    /// Push the current playback state and track to the tray tooltip, so it
+    /// reads e.g. "▶ Artist — Title" instead of the static "Amberol - Click
+    /// to restore" it's created with.
+    fn update_tooltip(&self) {
+        let playing = *self.state.borrow() == PlaybackState::Playing;
+        let (title, artist) = self.track.borrow().clone();
+        let track = (!title.is_empty()).then(|| format!("{artist} — {title}"));
+        self.app.update_tray_tooltip(playing, track.as_deref());
+    }
+
+    /// Redraw the tray icon, unless we already redrew it within
+    /// `MIN_UPDATE_INTERVAL`.
+    fn queue_redraw(&self) {
+        let now = Instant::now();
+        if let Some(last) = self.last_update.get() {
+            if now.duration_since(last) < MIN_UPDATE_INTERVAL {
+                return;
+            }
+        }
+        self.last_update.set(Some(now));
+        self.redraw();
+    }
+
+    fn redraw(&self) {
+        let state = self.state.borrow().clone();
+        let position = self.position.get();
+        let duration = self.duration.get();
+
+        debug!("Redrawing tray icon (state={state:?}, position={position}, duration={duration})");
+
+        self.app.update_tray_playback_icon(glib::clone!(
+            #[strong]
+            state,
+            move |size| {
+                let mut surface = IconRenderer::create_app_icon_surface(size)?;
+                let cr = cairo::Context::new(&surface).ok()?;
+
+                Self::draw_state_glyph(&cr, size, &state);
+                Self::draw_progress_arc(&cr, size, position, duration);
+
+                drop(cr);
+                Some(surface)
+            }
+        ));
+
+        self.refresh_x11_window_icons(state, position, duration);
+    }
+
+    /// Mirror the same progress overlay onto every window's `_NET_WM_ICON`,
+    /// so the taskbar/alt-tab icon tracks playback too, at the same
+    /// `MIN_UPDATE_INTERVAL` cadence as the tray icon (this is only called
+    /// from [`Self::redraw`], which [`Self::queue_redraw`] already throttles).
+    #[cfg(not(target_os = "windows"))]
+    fn refresh_x11_window_icons(&self, state: PlaybackState, position: u64, duration: u64) {
+        for window in self.app.windows() {
+            if let Some(window) = window.downcast_ref::<gtk::ApplicationWindow>() {
+                let state = state.clone();
+                IconRenderer::set_x11_window_icon_with(window, &move |size| {
+                    let mut surface = IconRenderer::create_app_icon_surface(size)?;
+                    let cr = cairo::Context::new(&surface).ok()?;
+
+                    Self::draw_state_glyph(&cr, size, &state);
+                    Self::draw_progress_arc(&cr, size, position, duration);
+
+                    drop(cr);
+                    Some(surface)
+                });
+            }
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    fn refresh_x11_window_icons(&self, _state: PlaybackState, _position: u64, _duration: u64) {}
+
+    /// Draw the playback glyph in the lower-right quadrant: a filled
+    /// triangle while paused, two bars while playing, and a stop square
+    /// when idle, inset from the icon's edge so it stays legible once the
+    /// base icon is composited underneath.
+    fn draw_state_glyph(cr: &cairo::Context, size: i32, state: &PlaybackState) {
+        let overlay = size as f64 * OVERLAY_SCALE;
+        let inset = overlay * 0.15;
+        let x0 = size as f64 - overlay - inset;
+        let y0 = size as f64 - overlay - inset;
+
+        cr.save().unwrap_or_default();
+        cr.set_source_rgb(1.0, 1.0, 1.0);
+
+        match state {
+            PlaybackState::Paused => {
+                cr.move_to(x0, y0);
+                cr.line_to(x0, y0 + overlay);
+                cr.line_to(x0 + overlay, y0 + overlay / 2.0);
+                cr.close_path();
+                cr.fill().unwrap_or_default();
+            }
+            PlaybackState::Playing => {
+                let bar_width = overlay * 0.3;
+                cr.rectangle(x0, y0, bar_width, overlay);
+                cr.fill().unwrap_or_default();
+                cr.rectangle(x0 + overlay - bar_width, y0, bar_width, overlay);
+                cr.fill().unwrap_or_default();
+            }
+            _ => {
+                cr.rectangle(x0, y0, overlay, overlay);
+                cr.fill().unwrap_or_default();
+            }
+        }
+
+        cr.restore().unwrap_or_default();
+    }
+
+    /// Draw a thin arc from -90deg sweeping clockwise by `2*pi *
+    /// position/duration`, so the tray icon shows progress through the
+    /// current track at a glance.
+    fn draw_progress_arc(cr: &cairo::Context, size: i32, position: u64, duration: u64) {
+        if duration == 0 {
+            return;
+        }
+
+        let fraction = (position as f64 / duration as f64).clamp(0.0, 1.0);
+        let start = -std::f64::consts::FRAC_PI_2;
+        let end = start + 2.0 * std::f64::consts::PI * fraction;
+
+        cr.save().unwrap_or_default();
+        cr.set_source_rgb(1.0, 1.0, 1.0);
+        cr.set_line_width(size as f64 * 0.08);
+        cr.arc(size as f64 / 2.0, size as f64 / 2.0, size as f64 / 2.0 - 1.0, start, end);
+        cr.stroke().unwrap_or_default();
+        cr.restore().unwrap_or_default();
+    }
+}
+
+impl Controller for PlaybackIconRenderer {
+    fn set_playback_state(&self, state: &PlaybackState) {
+        *self.state.borrow_mut() = state.clone();
+        if *state == PlaybackState::Stopped {
+            self.position.set(0);
+        }
+        self.queue_redraw();
+        self.update_tooltip();
+
+        // `AudioPlayer` doesn't expose queue-boundary information here yet,
+        // so Previous/Next stay enabled; only the Play/Pause label reflects
+        // real state for now.
+        self.app
+            .update_tray_menu_state(*state == PlaybackState::Playing, true, true);
+    }
+
+    fn set_song(&self, song: &Song) {
+        self.duration.set(song.duration());
+        self.position.set(0);
+        *self.track.borrow_mut() = (song.title(), song.artist());
+        self.update_tooltip();
+    }
+
+    fn set_position(&self, position: u64) {
+        self.position.set(position);
+        self.queue_redraw();
+    }
+
+    fn set_repeat_mode(&self, _repeat: RepeatMode) {}
+}
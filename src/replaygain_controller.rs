@@ -0,0 +1,45 @@
+// SPDX-FileCopyrightText: 2024  Emmanuele Bassi
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Applies each song's ReplayGain tag as a playback volume multiplier.
+//!
+//! [`ReplayGainController`] is a [`Controller`] like `MprisController` or
+//! `LinuxPowerManager`: `AudioPlayer` drives it with song changes, and it
+//! converts the tagged gain into a linear scale factor applied on top of
+//! the user's chosen volume, per the "replaygain-mode" setting (Off /
+//! Track / Album).
+
+use log::debug;
+
+use crate::{
+    application::Application,
+    audio::{replaygain::GainMode, Controller, PlaybackState, RepeatMode, Song},
+};
+
+pub struct ReplayGainController {
+    app: Application,
+}
+
+impl ReplayGainController {
+    pub fn new(app: &Application) -> Self {
+        Self { app: app.clone() }
+    }
+
+    fn mode(&self) -> GainMode {
+        GainMode::from_settings_str(&self.app.settings().string("replaygain-mode"))
+    }
+}
+
+impl Controller for ReplayGainController {
+    fn set_playback_state(&self, _state: &PlaybackState) {}
+
+    fn set_song(&self, song: &Song) {
+        let scale = song.volume_scale(self.mode());
+        debug!("Applying ReplayGain scale factor {scale:.3} ({:?})", self.mode());
+        self.app.player().set_volume_scale(scale);
+    }
+
+    fn set_position(&self, _position: u64) {}
+
+    fn set_repeat_mode(&self, _repeat: RepeatMode) {}
+}
@@ -0,0 +1,111 @@
+// SPDX-FileCopyrightText: 2024  Emmanuele Bassi
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+//! Keeps [`crate::audio::session_state`] up to date with the live playback
+//! session, so it can be restored the next time Amberol starts.
+//!
+//! [`SessionController`] is a [`Controller`] like `MprisController` or
+//! `LinuxPowerManager`: the player drives it with state, position, song and
+//! queue updates, and it persists a [`SessionState`] snapshot to disk on
+//! every meaningful change, throttled to [`SAVE_INTERVAL`] for the
+//! sub-second position updates that arrive while playing.
+
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+use log::debug;
+
+use crate::audio::{
+    session_state::{self, SerializableRepeatMode, SessionState},
+    Controller, PlaybackState, RepeatMode, Song,
+};
+
+/// Minimum spacing between position-triggered saves; `AudioPlayer` reports
+/// position far more often than the session file needs to be rewritten.
+const SAVE_INTERVAL: Duration = Duration::from_secs(1);
+
+pub struct SessionController {
+    queue_uris: RefCell<Vec<String>>,
+    current_index: Cell<Option<usize>>,
+    position: Cell<u64>,
+    repeat_mode: RefCell<RepeatMode>,
+    volume: Cell<f64>,
+    last_save: Cell<Option<Instant>>,
+}
+
+impl SessionController {
+    pub fn new() -> Self {
+        Self {
+            queue_uris: RefCell::new(Vec::new()),
+            current_index: Cell::new(None),
+            position: Cell::new(0),
+            repeat_mode: RefCell::new(RepeatMode::Consecutive),
+            volume: Cell::new(1.0),
+            last_save: Cell::new(None),
+        }
+    }
+
+    /// Update the queue exposed through [`SessionState`]. Called by the
+    /// player whenever the in-app queue changes, mirroring
+    /// `MprisController::update_queue`.
+    pub fn update_queue(&self, songs: &[Song]) {
+        *self.queue_uris.borrow_mut() = songs.iter().map(|s| s.uri()).collect();
+        self.save_now();
+    }
+
+    /// Called by the player whenever volume changes; persisted so a
+    /// restored session resumes at the same loudness.
+    pub fn set_volume(&self, volume: f64) {
+        self.volume.set(volume);
+    }
+
+    fn save_now(&self) {
+        let state = SessionState {
+            queue_uris: self.queue_uris.borrow().clone(),
+            current_index: self.current_index.get(),
+            position_secs: self.position.get(),
+            repeat_mode: SerializableRepeatMode::from(self.repeat_mode.borrow().clone()),
+            volume: self.volume.get(),
+        };
+        debug!("Persisting session state ({} queued)", state.queue_uris.len());
+        session_state::save(&state);
+        self.last_save.set(Some(Instant::now()));
+    }
+}
+
+impl Default for SessionController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Controller for SessionController {
+    fn set_playback_state(&self, _state: &PlaybackState) {
+        self.save_now();
+    }
+
+    fn set_song(&self, song: &Song) {
+        let uri = song.uri();
+        let index = self.queue_uris.borrow().iter().position(|u| *u == uri);
+        self.current_index.set(index);
+        self.position.set(0);
+        self.save_now();
+    }
+
+    fn set_position(&self, position: u64) {
+        self.position.set(position);
+
+        let now = Instant::now();
+        if let Some(last) = self.last_save.get() {
+            if now.duration_since(last) < SAVE_INTERVAL {
+                return;
+            }
+        }
+        self.save_now();
+    }
+
+    fn set_repeat_mode(&self, repeat: RepeatMode) {
+        *self.repeat_mode.borrow_mut() = repeat;
+        self.save_now();
+    }
+}
@@ -5,8 +5,10 @@
 pub mod windows_tray {
     use gtk::{glib, prelude::*};
     use log::{info, warn, error};
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
     use std::rc::Rc;
+    use async_channel::Sender;
+    use crate::application::ApplicationAction;
     use crate::icon_renderer::IconRenderer;
     use windows::Win32::{
         Foundation::{HWND, LPARAM, LRESULT, WPARAM, HINSTANCE, POINT},
@@ -14,8 +16,8 @@ pub mod windows_tray {
         System::LibraryLoader::GetModuleHandleW,
         UI::{
             Shell::{
-                Shell_NotifyIconW, NIF_ICON, NIF_MESSAGE, NIF_TIP, NIM_ADD, NIM_DELETE, 
-                NIM_MODIFY, NOTIFYICONDATAW,
+                Shell_NotifyIconW, NIF_ICON, NIF_INFO, NIF_MESSAGE, NIF_TIP, NIIF_USER, NIM_ADD,
+                NIM_DELETE, NIM_MODIFY, NOTIFYICONDATAW,
             },
             WindowsAndMessaging::{
                 CreateWindowExW, DefWindowProcW, DestroyIcon, DestroyWindow, LoadCursorW, PostQuitMessage, 
@@ -24,13 +26,40 @@ pub mod windows_tray {
                 WS_OVERLAPPEDWINDOW, HICON, LoadIconW, IDI_APPLICATION, WINDOW_EX_STYLE,
                 HMENU, LoadImageW, IMAGE_ICON, LR_LOADFROMFILE,
                 CreatePopupMenu, AppendMenuW, TrackPopupMenu, DestroyMenu, SetForegroundWindow,
-                MF_STRING, TPM_RIGHTBUTTON, TPM_RETURNCMD, WM_COMMAND, GetCursorPos,
+                MF_STRING, MF_SEPARATOR, MF_GRAYED, TPM_RIGHTBUTTON, TPM_RETURNCMD, WM_COMMAND, GetCursorPos,
+                RegisterWindowMessageW,
             },
         },
     };
 
     const WM_TRAYICON: u32 = WM_APP + 1;
 
+    const MENU_ID_PLAY_PAUSE: u32 = 1001;
+    const MENU_ID_PREVIOUS: u32 = 1002;
+    const MENU_ID_NEXT: u32 = 1003;
+    const MENU_ID_SHOW: u32 = 1004;
+    const MENU_ID_QUIT: u32 = 1005;
+
+    thread_local! {
+        // `window_proc` is a bare `extern "system" fn` with no instance
+        // pointer, so the only way to reach the application's action channel
+        // from it is through thread-local storage set up once in `new()`.
+        static ACTION_SENDER: RefCell<Option<Sender<ApplicationAction>>> = RefCell::new(None);
+        // The numeric id `RegisterWindowMessageW(w!("TaskbarCreated"))`
+        // resolves to, so `window_proc` can recognize the broadcast Explorer
+        // sends out after it restarts and re-add our tray icon.
+        static TASKBAR_CREATED_MSG: Cell<u32> = Cell::new(0);
+        // Tracks the `HICON` installed by the most recent `TaskbarCreated`
+        // re-registration, since that path runs outside any `SystemTray`
+        // instance and so can't update `self.custom_icon` directly.
+        static TRAY_ICON: RefCell<Option<HICON>> = RefCell::new(None);
+        // `(playing, has_previous, has_next)`, refreshed by
+        // `SystemTray::update_menu_state` and read by `show_context_menu`,
+        // which is itself a bare `window_proc` callee with no `self` to
+        // read live state from.
+        static MENU_STATE: Cell<(bool, bool, bool)> = Cell::new((false, true, true));
+    }
+
     pub struct SystemTray {
         hwnd: HWND,
         icon_id: u32,
@@ -46,31 +75,157 @@ pub mod windows_tray {
     }
 
     impl SystemTray {
-        pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        pub fn new(sender: Sender<ApplicationAction>) -> Result<Self, Box<dyn std::error::Error>> {
             info!("🔧 Creating Windows system tray");
-            
+
+            ACTION_SENDER.with(|cell| *cell.borrow_mut() = Some(sender));
+
             // Create a hidden window to receive tray messages
             let hwnd = unsafe { Self::create_hidden_window()? };
-            
+
             let mut tray = SystemTray {
                 hwnd,
                 icon_id: 1,
                 custom_icon: None,
             };
-            
+
             tray.add_to_tray()?;
             info!("✅ System tray created successfully");
-            
+
             Ok(tray)
         }
         
-        pub fn set_on_activate<F>(&mut self, _callback: F) 
-        where 
-            F: Fn() + 'static 
+        pub fn set_on_activate<F>(&mut self, _callback: F)
+        where
+            F: Fn() + 'static
         {
             // Callback is no longer stored, tray activation is handled directly
             // This method is kept for API compatibility
         }
+
+        /// The hidden window's handle, for Windows integrations that need
+        /// to attach to it directly (e.g. `SystemMediaTransportControls`).
+        pub fn hwnd(&self) -> isize {
+            self.hwnd.0 as isize
+        }
+
+        /// Pop a "Now Playing" balloon from the tray icon, with the cover art
+        /// (if any) as the balloon's own icon, so a song change is visible
+        /// even while the main window is hidden to the tray.
+        pub fn show_now_playing(
+            &self,
+            title: &str,
+            artist: &str,
+            album_art: Option<&mut gtk::cairo::ImageSurface>,
+        ) {
+            unsafe {
+                let mut nid = NOTIFYICONDATAW {
+                    cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+                    hWnd: self.hwnd,
+                    uID: self.icon_id,
+                    uFlags: NIF_INFO,
+                    ..Default::default()
+                };
+
+                let info_title: Vec<u16> = title.encode_utf16().collect();
+                let len = std::cmp::min(info_title.len(), nid.szInfoTitle.len() - 1);
+                nid.szInfoTitle[..len].copy_from_slice(&info_title[..len]);
+                nid.szInfoTitle[len] = 0;
+
+                let info: Vec<u16> = artist.encode_utf16().collect();
+                let len = std::cmp::min(info.len(), nid.szInfo.len() - 1);
+                nid.szInfo[..len].copy_from_slice(&info[..len]);
+                nid.szInfo[len] = 0;
+
+                let balloon_icon = album_art.and_then(IconRenderer::hicon_from_surface);
+                if let Some(hicon) = balloon_icon {
+                    nid.dwInfoFlags = NIIF_USER;
+                    nid.hBalloonIcon = hicon;
+                }
+
+                if !Shell_NotifyIconW(NIM_MODIFY, &nid).as_bool() {
+                    warn!("Failed to show now-playing balloon notification");
+                }
+
+                // The shell copies the icon into the balloon, so we still own
+                // this handle and must free it ourselves.
+                if let Some(hicon) = balloon_icon {
+                    let _ = DestroyIcon(hicon);
+                }
+            }
+        }
+
+        /// Cache the live playback state `show_context_menu` reads each time
+        /// it rebuilds the menu, so a right-click always shows the current
+        /// Play/Pause label and greys out Previous/Next when there is no
+        /// adjacent track to jump to.
+        pub fn update_menu_state(&self, playing: bool, has_previous: bool, has_next: bool) {
+            MENU_STATE.with(|cell| cell.set((playing, has_previous, has_next)));
+        }
+
+        /// Rebuild the tray tooltip from the current playback state and
+        /// track, replacing the static "Amberol - Click to restore" text
+        /// `register_tray_icon` installs at startup.
+        pub fn update_tooltip(&self, playing: bool, track: Option<&str>) {
+            let tooltip = match track {
+                Some(track) if playing => format!("▶ {track}"),
+                Some(track) => format!("⏸ {track}"),
+                None => "Amberol - Click to restore".to_string(),
+            };
+
+            unsafe {
+                let mut nid = NOTIFYICONDATAW {
+                    cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+                    hWnd: self.hwnd,
+                    uID: self.icon_id,
+                    uFlags: NIF_TIP,
+                    ..Default::default()
+                };
+
+                let tooltip_wide: Vec<u16> = tooltip.encode_utf16().collect();
+                let len = std::cmp::min(tooltip_wide.len(), nid.szTip.len() - 1);
+                nid.szTip[..len].copy_from_slice(&tooltip_wide[..len]);
+                nid.szTip[len] = 0;
+
+                if !Shell_NotifyIconW(NIM_MODIFY, &nid).as_bool() {
+                    warn!("Failed to update tray tooltip");
+                }
+            }
+        }
+
+        /// Re-render the tray icon and push it via `Shell_NotifyIconW`
+        /// (`NIM_MODIFY`), replacing whichever `HICON` we handed the shell
+        /// last time.
+        pub fn update_playback_icon(&mut self, render: &dyn Fn(i32) -> Option<gtk::cairo::ImageSurface>) {
+            let Some(mut surface) = render(32) else {
+                return;
+            };
+            let Some(hicon) = IconRenderer::hicon_from_surface(&mut surface) else {
+                warn!("Failed to render playback tray icon");
+                return;
+            };
+
+            unsafe {
+                let nid = NOTIFYICONDATAW {
+                    cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+                    hWnd: self.hwnd,
+                    uID: self.icon_id,
+                    uFlags: NIF_ICON,
+                    hIcon: hicon,
+                    ..Default::default()
+                };
+
+                if !Shell_NotifyIconW(NIM_MODIFY, &nid).as_bool() {
+                    warn!("Failed to update tray icon");
+                }
+            }
+
+            if let Some(old_icon) = self.custom_icon.replace(hicon) {
+                unsafe {
+                    let _ = DestroyIcon(old_icon);
+                }
+            }
+        }
         
         unsafe fn create_hidden_window() -> Result<HWND, Box<dyn std::error::Error>> {
             let class_name = windows::core::w!("AmberolTrayClass");
@@ -93,7 +248,14 @@ pub mod windows_tray {
             };
             
             RegisterClassExW(&wc);
-            
+
+            // Explorer broadcasts this to every top-level window when it
+            // restarts after a crash; `window_proc` watches for it to
+            // re-register the tray icon it would otherwise lose forever.
+            TASKBAR_CREATED_MSG.with(|cell| {
+                cell.set(RegisterWindowMessageW(windows::core::w!("TaskbarCreated")))
+            });
+
             let hwnd = CreateWindowExW(
                 WINDOW_EX_STYLE::default(),
                 class_name,
@@ -113,43 +275,54 @@ pub mod windows_tray {
         }
         
         fn add_to_tray(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-            unsafe {
-                let mut nid = NOTIFYICONDATAW {
-                    cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
-                    hWnd: self.hwnd,
-                    uID: self.icon_id,
-                    uFlags: NIF_ICON | NIF_MESSAGE | NIF_TIP,
-                    uCallbackMessage: WM_TRAYICON,
-                    hIcon: {
-                        // Try to use our custom tray icon, fallback to default
-                        use crate::icon_renderer::IconRenderer;
-                        if let Some(custom_icon) = IconRenderer::create_tray_icon() {
-                            info!("🎨 Using custom tray icon");
-                            self.custom_icon = Some(custom_icon);
-                            custom_icon
-                        } else {
-                            warn!("⚠️ Failed to create custom tray icon, using default");
-                            LoadIconW(None, IDI_APPLICATION)?
-                        }
-                    },
-                    ..Default::default()
-                };
-                
-                // Set tooltip text
-                let tooltip = "Amberol - Click to restore";
-                let tooltip_wide: Vec<u16> = tooltip.encode_utf16().collect();
-                let len = std::cmp::min(tooltip_wide.len(), nid.szTip.len() - 1);
-                nid.szTip[..len].copy_from_slice(&tooltip_wide[..len]);
-                nid.szTip[len] = 0; // Null terminate
-                
-                let result = Shell_NotifyIconW(NIM_ADD, &nid);
-                if result.as_bool() == false {
-                    return Err("Failed to add system tray icon".into());
-                }
-            }
-            
+            self.custom_icon = unsafe { Self::register_tray_icon(self.hwnd, self.icon_id)? };
             Ok(())
         }
+
+        /// Build and submit the `NOTIFYICONDATAW` that puts our icon in the
+        /// tray (`NIM_ADD`), returning the custom `HICON` on success so the
+        /// caller can track it for cleanup. Shared between `add_to_tray` and
+        /// `window_proc`'s `TaskbarCreated` recovery path, since both need to
+        /// (re-)register the same icon from scratch.
+        unsafe fn register_tray_icon(
+            hwnd: HWND,
+            icon_id: u32,
+        ) -> Result<Option<HICON>, Box<dyn std::error::Error>> {
+            let mut custom_icon = None;
+
+            let mut nid = NOTIFYICONDATAW {
+                cbSize: std::mem::size_of::<NOTIFYICONDATAW>() as u32,
+                hWnd: hwnd,
+                uID: icon_id,
+                uFlags: NIF_ICON | NIF_MESSAGE | NIF_TIP,
+                uCallbackMessage: WM_TRAYICON,
+                hIcon: {
+                    // Try to use our custom tray icon, fallback to default
+                    if let Some(icon) = IconRenderer::create_tray_icon() {
+                        info!("🎨 Using custom tray icon");
+                        custom_icon = Some(icon);
+                        icon
+                    } else {
+                        warn!("⚠️ Failed to create custom tray icon, using default");
+                        LoadIconW(None, IDI_APPLICATION)?
+                    }
+                },
+                ..Default::default()
+            };
+
+            // Set tooltip text
+            let tooltip = "Amberol - Click to restore";
+            let tooltip_wide: Vec<u16> = tooltip.encode_utf16().collect();
+            let len = std::cmp::min(tooltip_wide.len(), nid.szTip.len() - 1);
+            nid.szTip[..len].copy_from_slice(&tooltip_wide[..len]);
+            nid.szTip[len] = 0; // Null terminate
+
+            if !Shell_NotifyIconW(NIM_ADD, &nid).as_bool() {
+                return Err("Failed to add system tray icon".into());
+            }
+
+            Ok(custom_icon)
+        }
         
         unsafe extern "system" fn window_proc(
             hwnd: HWND, 
@@ -188,40 +361,50 @@ pub mod windows_tray {
                 }
                 WM_COMMAND => {
                     let command_id = (wparam.0 & 0xFFFF) as u32;
-                    match command_id {
-                        1001 => {
-                            // Restore/Show window
-                            info!("📱 Context menu: Restore selected");
-                            glib::idle_add_once(|| {
-                                if let Some(app) = gtk::gio::Application::default() {
-                                    app.activate();
-                                }
-                                glib::ControlFlow::Continue
-                            });
-                        }
-                        1002 => {
-                            // Quit application
-                            info!("🚪 Context menu: Quit selected");
-                            glib::idle_add_once(|| {
-                                if let Some(app) = gtk::gio::Application::default() {
-                                    app.quit();
-                                    info!("📱 Application quit requested");
-                                }
-                                glib::ControlFlow::Continue
-                            });
-                        }
-                        _ => {}
+                    let action = match command_id {
+                        MENU_ID_PLAY_PAUSE => Some(ApplicationAction::PlayPause),
+                        MENU_ID_PREVIOUS => Some(ApplicationAction::Previous),
+                        MENU_ID_NEXT => Some(ApplicationAction::Next),
+                        MENU_ID_SHOW => Some(ApplicationAction::Present),
+                        MENU_ID_QUIT => Some(ApplicationAction::Quit),
+                        _ => None,
+                    };
+
+                    if let Some(action) = action {
+                        info!("📱 Context menu: sending tray action");
+                        Self::send_action(action);
                     }
                 }
                 WM_DESTROY => {
                     PostQuitMessage(0);
                 }
+                _ if msg != 0 && msg == TASKBAR_CREATED_MSG.with(|cell| cell.get()) => {
+                    info!("🔁 Explorer restarted (TaskbarCreated), re-registering tray icon");
+                    match Self::register_tray_icon(hwnd, 1) {
+                        Ok(icon) => TRAY_ICON.with(|cell| *cell.borrow_mut() = icon),
+                        Err(e) => warn!("Failed to re-register tray icon: {e}"),
+                    }
+                }
                 _ => return DefWindowProcW(hwnd, msg, wparam, lparam),
             }
             
             LRESULT(0)
         }
         
+        /// Send an `ApplicationAction` over the channel set up in `new()`, so
+        /// the tray drives real playback control through the same
+        /// action-loop `startup()` already drains, instead of reaching for
+        /// `GApplication` directly.
+        fn send_action(action: ApplicationAction) {
+            ACTION_SENDER.with(|cell| {
+                if let Some(sender) = cell.borrow().as_ref() {
+                    if let Err(e) = sender.send_blocking(action) {
+                        warn!("Failed to send tray action: {e}");
+                    }
+                }
+            });
+        }
+
         /// Show context menu for tray icon
         unsafe fn show_context_menu(hwnd: HWND) {
             let hmenu = CreatePopupMenu();
@@ -229,14 +412,29 @@ pub mod windows_tray {
                 warn!("Failed to create popup menu");
                 return;
             }
-            
-            // Add menu items
+
+            // Rebuilt from scratch on every right-click, so it always
+            // reflects whatever `update_menu_state` last cached rather than
+            // the state at the moment the tray was created.
+            let (playing, has_previous, has_next) = MENU_STATE.with(|cell| cell.get());
+
+            let play_pause_label = if playing { "Pause\0" } else { "Play\0" };
+            let play_pause_text: Vec<u16> = play_pause_label.encode_utf16().collect();
+            let previous_text: Vec<u16> = "Previous\0".encode_utf16().collect();
+            let next_text: Vec<u16> = "Next\0".encode_utf16().collect();
             let restore_text: Vec<u16> = "Show Amberol\0".encode_utf16().collect();
             let quit_text: Vec<u16> = "Quit\0".encode_utf16().collect();
-            
-            AppendMenuW(hmenu, MF_STRING, 1001, windows::core::PCWSTR(restore_text.as_ptr()));
-            AppendMenuW(hmenu, MF_STRING, 1002, windows::core::PCWSTR(quit_text.as_ptr()));
-            
+
+            let previous_flags = if has_previous { MF_STRING } else { MF_STRING | MF_GRAYED };
+            let next_flags = if has_next { MF_STRING } else { MF_STRING | MF_GRAYED };
+
+            AppendMenuW(hmenu, MF_STRING, MENU_ID_PLAY_PAUSE as usize, windows::core::PCWSTR(play_pause_text.as_ptr()));
+            AppendMenuW(hmenu, previous_flags, MENU_ID_PREVIOUS as usize, windows::core::PCWSTR(previous_text.as_ptr()));
+            AppendMenuW(hmenu, next_flags, MENU_ID_NEXT as usize, windows::core::PCWSTR(next_text.as_ptr()));
+            AppendMenuW(hmenu, MF_SEPARATOR, 0, windows::core::PCWSTR::null());
+            AppendMenuW(hmenu, MF_STRING, MENU_ID_SHOW as usize, windows::core::PCWSTR(restore_text.as_ptr()));
+            AppendMenuW(hmenu, MF_STRING, MENU_ID_QUIT as usize, windows::core::PCWSTR(quit_text.as_ptr()));
+
             // Get cursor position
             let mut pt = POINT { x: 0, y: 0 };
             GetCursorPos(&mut pt);
@@ -263,8 +461,9 @@ pub mod windows_tray {
             
             DestroyMenu(hmenu);
         }
-        
-        impl Drop for SystemTray {
+    }
+
+    impl Drop for SystemTray {
         fn drop(&mut self) {
             info!("🗑️ Removing system tray icon");
             unsafe {
@@ -274,37 +473,203 @@ pub mod windows_tray {
                     uID: self.icon_id,
                     ..Default::default()
                 };
-                
+
                 let _ = Shell_NotifyIconW(NIM_DELETE, &nid);
-                
+
                 // Clean up custom icon if we created one
                 if let Some(icon) = self.custom_icon {
                     let _ = DestroyIcon(icon);
                     info!("🗑️ Cleaned up custom tray icon");
                 }
-                
+
                 let _ = DestroyWindow(self.hwnd);
             }
         }
     }
 }
 
+/// Fixed meter-fill color for the tray volume-meter overlay: the same
+/// warm orange `IconRenderer` fills its tray progress bar with.
+const METER_COLOR: (u8, u8, u8) = (245, 121, 0);
+
+/// Where the meter bar sits on the icon, as a percentage of its
+/// drawable size — a narrow strip along the right edge, like a VU meter.
+const METER_X_OFFSET_PCT: f64 = 0.82;
+const METER_Y_OFFSET_PCT: f64 = 0.08;
+const METER_WIDTH_PCT: f64 = 0.12;
+const METER_HEIGHT_PCT: f64 = 0.84;
+
+/// Composites a small volume-level bar over whatever `IconRenderer`
+/// surface the tray is about to show, the way standalone mixer applets
+/// meter loudness at a glance. Platform-independent: both the Windows and
+/// Linux/FreeBSD `SystemTray` backends render through
+/// `Application::update_tray_playback_icon`, which applies this overlay
+/// once for both rather than each backend compositing its own.
+pub struct VolumeMeterOverlay {
+    enabled: std::cell::Cell<bool>,
+    volume: std::cell::Cell<f64>,
+    /// Composited surfaces, keyed by `(size, volume percentage point)`, so
+    /// redrawing at an unchanged size and volume is free.
+    cache: std::cell::RefCell<std::collections::HashMap<(i32, i32), gtk::cairo::ImageSurface>>,
+}
+
+impl std::fmt::Debug for VolumeMeterOverlay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VolumeMeterOverlay")
+            .field("enabled", &self.enabled.get())
+            .field("volume", &self.volume.get())
+            .finish()
+    }
+}
+
+impl VolumeMeterOverlay {
+    pub fn new() -> Self {
+        Self {
+            enabled: std::cell::Cell::new(true),
+            volume: std::cell::Cell::new(1.0),
+            cache: std::cell::RefCell::new(std::collections::HashMap::new()),
+        }
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        if self.enabled.replace(enabled) != enabled {
+            self.cache.borrow_mut().clear();
+        }
+    }
+
+    /// `volume` is 0.0-1.0; out-of-range values are clamped.
+    pub fn set_volume(&self, volume: f64) {
+        self.volume.set(volume.clamp(0.0, 1.0));
+    }
+
+    /// Composite the meter onto `base` unless the overlay is disabled,
+    /// reusing a cached surface for this `size` and the current volume's
+    /// whole percentage point.
+    pub fn composite(&self, size: i32, mut base: gtk::cairo::ImageSurface) -> gtk::cairo::ImageSurface {
+        if !self.enabled.get() {
+            return base;
+        }
+
+        let percent = (self.volume.get() * 100.0).round() as i32;
+        let key = (size, percent);
+        if let Some(cached) = self.cache.borrow().get(&key) {
+            return cached.clone();
+        }
+
+        Self::paint_meter(&mut base, size, self.volume.get());
+        self.cache.borrow_mut().insert(key, base.clone());
+        base
+    }
+
+    /// Fill the meter region bottom-up to `round(volume * drawable_height)`
+    /// pixels, as a row fill directly in the surface's pixel data rather
+    /// than a `cairo` path fill.
+    fn paint_meter(surface: &mut gtk::cairo::ImageSurface, size: i32, volume: f64) {
+        surface.flush();
+        let stride = surface.stride();
+        let Ok(mut data) = surface.data() else {
+            return;
+        };
+
+        let x0 = (size as f64 * METER_X_OFFSET_PCT).round() as i32;
+        let y0 = (size as f64 * METER_Y_OFFSET_PCT).round() as i32;
+        let width = ((size as f64 * METER_WIDTH_PCT).round() as i32).max(1);
+        let height = ((size as f64 * METER_HEIGHT_PCT).round() as i32).max(1);
+        let fill_height = (volume.clamp(0.0, 1.0) * height as f64).round() as i32;
+
+        let (r, g, b) = METER_COLOR;
+        for y in (y0 + height - fill_height)..(y0 + height) {
+            if y < 0 || y >= size {
+                continue;
+            }
+            for x in x0..(x0 + width).min(size) {
+                let offset = (y * stride + x * 4) as usize;
+                if offset + 4 > data.len() {
+                    continue;
+                }
+                // cairo's ARGB32 is premultiplied and native-endian, i.e.
+                // stored as B, G, R, A on little-endian hosts.
+                data[offset] = b;
+                data[offset + 1] = g;
+                data[offset + 2] = r;
+                data[offset + 3] = 255;
+            }
+        }
+    }
+}
+
 #[cfg(not(target_os = "windows"))]
 pub mod windows_tray {
+    /// Linux/FreeBSD backend: a real freedesktop StatusNotifierItem tray,
+    /// giving this platform the same background media controls as Windows.
     #[derive(Debug)]
-    pub struct SystemTray;
-    
+    pub struct SystemTray(Option<crate::linux_tray::LinuxTray>);
+
     impl SystemTray {
-        pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
-            Ok(SystemTray)
+        /// `sender` is unused here: `LinuxTray`'s dbusmenu activates the
+        /// GAction of the same name on the default `GApplication` (see
+        /// `linux_tray.rs`) instead of sending over the `ApplicationAction`
+        /// channel. Only `quit` and `about` are registered there, so the
+        /// Play/Pause, Previous and Next items find no action and do nothing.
+        pub fn new(
+            _sender: async_channel::Sender<crate::application::ApplicationAction>,
+        ) -> Result<Self, Box<dyn std::error::Error>> {
+            Ok(SystemTray(crate::linux_tray::LinuxTray::new()))
         }
-        
-        pub fn set_on_activate<F>(&mut self, _callback: F) 
-        where 
-            F: Fn() + 'static 
+
+        pub fn set_on_activate<F>(&mut self, _callback: F)
+        where
+            F: Fn() + 'static,
         {
-            // No-op on non-Windows platforms
+            // `LinuxTray` raises the main window itself in response to the
+            // D-Bus `Activate` method, so there is no separate callback hook.
+        }
+
+        /// Re-render the tray icon at the sizes the StatusNotifierItem host
+        /// asks for and push it over D-Bus. `render` is called once per
+        /// size, since hosts are free to pick whichever is the closest fit.
+        pub fn update_playback_icon(
+            &self,
+            render: &dyn Fn(i32) -> Option<gtk::cairo::ImageSurface>,
+        ) {
+            let Some(tray) = &self.0 else {
+                return;
+            };
+
+            let pixmap = [22, 32, 48]
+                .into_iter()
+                .filter_map(|size| {
+                    let surface = render(size)?;
+                    crate::linux_tray::LinuxTray::surface_to_pixmap(size, surface)
+                })
+                .collect();
+
+            tray.update_icon_pixmap(pixmap);
         }
+
+        /// Push the current playback state and track to the `ToolTip`
+        /// property, so a status bar host showing it on hover reflects what
+        /// is actually playing.
+        pub fn update_tooltip(&self, playing: bool, track: Option<&str>) {
+            let Some(tray) = &self.0 else {
+                return;
+            };
+
+            let description = match track {
+                Some(track) if playing => format!("▶ {track}"),
+                Some(track) => format!("⏸ {track}"),
+                None => String::new(),
+            };
+
+            tray.update_tooltip(playing, description);
+        }
+
+        /// No-op here: the `dbusmenu` menu this platform exports is already
+        /// rebuilt fresh by the host on every open (see
+        /// `linux_tray::DBusMenu::get_layout`), so there is no cached state
+        /// for a right-click to go stale against the way Windows' native
+        /// popup menu would.
+        pub fn update_menu_state(&self, _playing: bool, _has_previous: bool, _has_next: bool) {}
     }
 }
 
@@ -17,6 +17,7 @@ use log::debug;
 
 /// Windows-specific power management
 #[cfg(target_os = "windows")]
+#[derive(Debug)]
 pub struct WindowsPowerManager {
     execution_state: EXECUTION_STATE,
 }